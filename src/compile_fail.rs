@@ -0,0 +1,362 @@
+//! Code which should be rejected by the derive, or fail to compile because of what it generates.
+//! This module only exists so that these examples get run as doctests.
+//!
+//! Only structs are supported.
+//! The messages for these first cases are also checked by the UI tests in `tests/ui`.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! enum Example {
+//!     A(u32),
+//!     B(u32),
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! union Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! Unit structs have no fields to index.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Example;
+//! ```
+//!
+//! All the fields must have the same type.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Example {
+//!     a: u32,
+//!     b: i64,
+//! }
+//! ```
+//!
+//! At least one field is required.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Empty {}
+//! ```
+//!
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Empty();
+//! ```
+//!
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Empty(#[named_array(skip)] u32);
+//! ```
+//!
+//! If any field has an `index`, every field must.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Example {
+//!     #[named_array(index = 1)]
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! The indices must be a permutation of `0..LEN`, so they can't be repeated,
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Example {
+//!     #[named_array(index = 0)]
+//!     a: u32,
+//!     #[named_array(index = 0)]
+//!     b: u32,
+//! }
+//! ```
+//!
+//! or leave gaps.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Example {
+//!     #[named_array(index = 0)]
+//!     a: u32,
+//!     #[named_array(index = 2)]
+//!     b: u32,
+//! }
+//! ```
+//!
+//! `index_type` only accepts integers other than `usize`, or types which can be converted into one.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(index_type = f32)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(index_type = usize)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! `contiguous` requires a `#[repr(C)]` or `#[repr(transparent)]` struct.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(contiguous)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! `contiguous` is also rejected on packed structs.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(contiguous)]
+//! #[repr(C, packed)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! Skipped fields would leave gaps in a `contiguous` struct.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(contiguous)]
+//! #[repr(C)]
+//! struct Example {
+//!     a: u32,
+//!     #[named_array(skip)]
+//!     b: u32,
+//!     c: u32,
+//! }
+//! ```
+//!
+//! Reordering fields would make the indices disagree with the slice view.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(contiguous)]
+//! #[repr(C)]
+//! struct Example {
+//!     #[named_array(index = 1)]
+//!     a: u32,
+//!     #[named_array(index = 0)]
+//!     b: u32,
+//! }
+//! ```
+//!
+//! Trait objects can't be viewed as a slice.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(contiguous, dyn = core::fmt::Debug)]
+//! #[repr(C)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! Without `contiguous`, [`AsRef<[T]>`](AsRef) is not implemented.
+//! ```compile_fail,E0277
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[repr(C)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//!
+//! fn sum(values: impl AsRef<[u32]>) -> u32 {
+//!     values.as_ref().iter().sum()
+//! }
+//!
+//! sum(Example { a: 1, b: 2 });
+//! ```
+//!
+//! The index enum must be renamed to a valid identifier.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(rename_index_enum = "not an ident")]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! Tuple structs have no index enum to rename.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(rename_index_enum = "Fields")]
+//! struct Example(u32, u32);
+//! ```
+//!
+//! A `readonly` struct can't be mutated through indexing,
+//! ```compile_fail,E0594
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(readonly)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//!
+//! let mut example = Example { a: 1, b: 2 };
+//! example[0] = 3;
+//! ```
+//!
+//! or any of the other methods.
+//! ```compile_fail,E0599
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(readonly)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//!
+//! let mut example = Example { a: 1, b: 2 };
+//! example.swap(0, 1);
+//! ```
+//!
+//! `flat` requires `contiguous`,
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(flat)]
+//! #[repr(C)]
+//! struct Example {
+//!     a: [u8; 4],
+//!     b: [u8; 4],
+//! }
+//! ```
+//!
+//! and fields which are arrays.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(contiguous, flat)]
+//! #[repr(C)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! A field which doesn't match the `output` type is still rejected, by the generated code.
+//! ```compile_fail,E0308
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(output = u32)]
+//! struct Example {
+//!     a: u32,
+//!     b: i64,
+//! }
+//! ```
+//!
+//! With `expect_len`, the number of fields must match it.
+//! ```compile_fail,E0080
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(expect_len = 3)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! With `chunk`, the number of fields must be a multiple of the chunk size.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(chunk = 2)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//!     c: u32,
+//! }
+//! ```
+//!
+//! Out of bounds indices can't both wrap and saturate.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(wrapping, saturating)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! A custom `oob_message` needs a single `{}` for the index.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(oob_message = "out of bounds")]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! `pod` needs the layout of the struct to be guaranteed by its `repr`.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(Clone, Copy, named_array)]
+//! #[named_array(pod)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! ```
+//!
+//! Without `no_move`, the generated code moves fields out of the struct, which isn't allowed if it
+//! implements `Drop`.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Example {
+//!     a: String,
+//!     b: String,
+//! }
+//!
+//! impl Drop for Example {
+//!     fn drop(&mut self) {}
+//! }
+//! ```
+//!
+//! With an integer `index_type` other than `i32`, unsuffixed literals fall back to `i32`, which
+//! can't be used as an index.
+//! ```compile_fail
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(index_type = u8)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//!
+//! let example = Example { a: 1, b: 2 };
+//! let _ = example[1];
+//! ```
@@ -8,8 +8,93 @@
 //! will be rejected.
 //! This is because type information does not exist at the time of macro expansion, so there is no
 //! way to confirm that the two refer to the same type.
+//! Only the tokens are compared, so differences in spacing, such as between `Vec<u32>` and
+//! `Vec< u32 >`, don't matter.
+//! If the types are known to be the same, adding `#[named_array(unchecked_types)]` skips this check.
+//! The first field's type is then used for all of them, so if they really are different, the
+//! generated code fails to compile instead.
+//! Alternatively, `#[named_array(output = Type)]` gives the element type to use, in place of the
+//! first field's type, and skips the check in the same way.
+//!
+//! Generic structs are supported, including lifetime and const parameters and defaults, as long as the
+//! fields are all written as the same type, such as `T` or `[u8; N]`.
+//!
+//! The generated code is emitted in the same module as the struct, so its fields don't need to be
+//! public.
+//! Methods which build a new struct from an existing one, such as `map`, can therefore be used
+//! anywhere the struct is visible, even from other crates.
 //!
 //! Indexing will panic if the index is out of bounds.
+//! The message can be replaced with `#[named_array(oob_message = "...")]`, in which `{}` is filled in
+//! with the index.
+//! For cyclic data, adding `#[named_array(wrapping)]` makes indexing wrap around instead, by taking
+//! the index modulo `LEN`.
+//! Negative indices of other index types still panic.
+//! Alternatively, `#[named_array(saturating)]` clamps out of bounds indices to the last field, and
+//! negative ones to the first, so that indexing never panics.
+//!
+//! For non-panicking access, `get` and `get_mut` methods are also generated, which return `None`
+//! if the index is out of bounds.
+//! The `try_index` and `try_index_mut` methods are similar, but return the out of bounds index as
+//! the error instead.
+//! An index can also be made valid beforehand with `clamp_index`, which clamps it to the last field.
+//! Mutable references to several different fields can be taken at once with `get_many_mut`, or to
+//! two of them with `two_mut`.
+//! The `get_const` method indexes like [`Index`], but can also be used in const contexts.
+//! Since there is always at least one field, `first` and `last` (and their `_mut` variants) return
+//! references directly.
+//! Similarly, `split_first` and `split_last` return one of them along with an array of the rest.
+//! Each pair of adjacent fields can be borrowed at once with `pairs`.
+//! The fields of two instances of the struct can be paired up with `zip`.
+//! Adding `#[named_array(chunk = N)]` also generates `chunks`, which groups references to the fields
+//! into arrays of `N` consecutive fields, such as the rows of a matrix.
+//! The number of fields must be a multiple of `N`.
+//!
+//! The number of fields is available as the `LEN` associated constant, which can be used in const
+//! contexts such as array lengths, and through the `len` and `is_empty` methods.
+//! The names of the fields are returned by `field_names`, in index order.
+//! Adding `#[named_array(expect_len = N)]` makes any other number of fields a compile error, to catch
+//! fields being added or removed by accident.
+//!
+//! The struct, as well as references to it, can also be iterated over with [`IntoIterator`], yielding the
+//! fields in index order. The `iter` and `iter_mut` methods are provided as shorthands for the
+//! reference iterators, and `reversed` returns an array of references in the opposite order.
+//! `indexed_iter` pairs each reference with its index.
+//! The iterators are the standard array iterators, such as [`core::array::IntoIter<&T, N>`], so they
+//! can be named in other types, using `LEN` for `N`.
+//! Adding `#[named_array(iter_types)]` generates named iterator types instead, prefixed with the
+//! name of the struct and with the same visibility, so that the length doesn't need to be spelled out.
+//! For a struct `Example`, `iter` returns an `ExampleIter<'_>`, `iter_mut` an `ExampleIterMut<'_>`,
+//! and `into_iter` an `ExampleIntoIter`, each also taking the generic parameters of the struct.
+//!
+//! Fields can be swapped by index with the `swap` method, which panics if either index is out of
+//! bounds, or `checked_swap`, which returns the out of bounds index instead.
+//! The `replace` method swaps a new value into a field, returning the old one.
+//! The fields can also be rotated in place with `rotate_left` and `rotate_right`.
+//!
+//! The `map` method applies a function to every field, in index order, returning a new struct,
+//! while `for_each` updates the fields in place, and `fill` sets all of them to the same value.
+//! For fallible functions, `try_map` stops at the first error and returns it instead.
+//!
+//! If the fields can be compared with [`PartialEq`], `position` returns the index of the first
+//! field equal to a given value, and `contains` checks whether there is one.
+//! More generally, `count_where` counts the fields which satisfy a predicate.
+//! If they are [`Ord`], `min` and `max` return the smallest and largest fields.
+//! Their indices are returned by `position_min` and `position_max`, with ties going to the lowest
+//! index.
+//! Numeric fields can be added together with `sum`, or multiplied with `product`.
+//! The `dot` method multiplies the fields of two structs pairwise, and adds up the results.
+//!
+//! The struct can be converted into an array of its fields with [`From`], and back again if none
+//! of its fields are skipped, and it isn't `#[non_exhaustive]`, since that would let other crates
+//! build it.
+//! In that case, it can also be built from a slice of the right length with [`TryFrom`], which clones
+//! the elements, or returns an error named after the struct with a `LengthError` suffix.
+//! If that name is already taken, the error can be named with
+//! `#[named_array(rename_length_error = "Name")]`.
+//! If the fields are [`Copy`], `as_array` (or `copied`) copies them into an array without consuming
+//! the struct, and `cloned` does the same for [`Clone`] fields.
+//! With the `alloc` feature enabled, the struct can also be converted into a `Vec` of its fields.
 //!
 //! # Example
 //! ```rust
@@ -46,170 +131,2688 @@
 //! # }
 //! ```
 //!
+//! # Slice access
+//!
+//! If the struct is `#[repr(C)]` (or `#[repr(transparent)]`), the fields are guaranteed to be laid out
+//! back to back in memory, since they all have the same type.
+//! Adding `#[named_array(contiguous)]` asserts this, and generates `as_slice` and `as_mut_slice`
+//! methods which view the fields as a slice without copying, along with impls of [`AsRef<[T]>`]
+//! and [`AsMut<[T]>`].
+//! Ranges of fields can also be indexed, as in `example[1..3]`, which panics like slice indexing
+//! if the range is out of bounds.
+//! Without the attribute, these methods are not generated.
+//!
+//! ```rust
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(contiguous)]
+//! #[repr(C)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//!     c: u32,
+//! }
+//! # fn main() {
+//! let example = Example { a: 1, b: 2, c: 3 };
+//! assert_eq!(example.as_slice(), &[1, 2, 3]);
+//! # }
+//! ```
+//!
+//! If the fields are arrays, such as `[u8; 4]`, adding `flat` as well, as in
+//! `#[named_array(contiguous, flat)]`, generates `flat` and `flat_mut` methods, which view the
+//! elements of all the arrays as one slice.
+//! Arrays have no padding between their elements, so this relies on the same layout guarantees.
+//!
+//! For a newtype around a single array or slice, such as `struct Buf([u8; 8])`,
+//! `#[named_array(newtype)]` instead generates `as_slice` and `as_mut_slice` methods which view the
+//! elements of that field, along with the `AsRef` and `AsMut` impls, without needing a `repr`.
+//!
+//! # Skipping fields
+//!
+//! Fields can be left out of the array view with `#[named_array(skip)]`.
+//! Skipped fields are not included in the indices or `LEN`, and do not need to have the same type as
+//! the other fields.
+//! Fields which are disabled with `#[cfg(...)]` are removed before the macro sees the struct, so they
+//! are left out in the same way, whenever they are disabled.
+//!
+//! ```rust
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Example {
+//!     a: u32,
+//!     #[named_array(skip)]
+//!     generation: u64,
+//!     b: u32,
+//! }
+//! # fn main() {
+//! let example = Example { a: 1, generation: 0, b: 2 };
+//! assert_eq!(Example::LEN, 2);
+//! assert_eq!(example[1], example.b);
+//! # }
+//! ```
+//!
+//! # Reordering fields
+//!
+//! By default, the indices follow the order in which the fields are declared.
+//! This can be overridden by giving every field an `#[named_array(index = N)]` attribute.
+//! The indices must then cover `0..LEN` exactly once each.
+//!
+//! ```rust
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Example {
+//!     #[named_array(index = 1)]
+//!     a: u32,
+//!     #[named_array(index = 0)]
+//!     b: u32,
+//! }
+//! # fn main() {
+//! let example = Example { a: 1, b: 2 };
+//! assert_eq!(example[0], example.b);
+//! assert_eq!(example[1], example.a);
+//! # }
+//! ```
+//!
+//! # Index enums
+//!
+//! For structs with named fields, an enum named after the struct with an `Index` suffix is also
+//! generated, with a variant for each field.
+//! The variants are the field names converted to `PascalCase`, and can be used in place of `usize`
+//! indices.
+//! If the name collides with another type, a different one can be given with
+//! `#[named_array(rename_index_enum = "Name")]`.
+//! If the field names can't all be turned into distinct variants, such as for a field named `__`,
+//! or for both `x` and `x_`, the enum is left out, unless it was asked for with `rename_index_enum`,
+//! in which case the fields must be renamed.
+//!
+//! ```rust
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! struct Example {
+//!     first: u32,
+//!     second_field: u32,
+//! }
+//! # fn main() {
+//! let example = Example { first: 1, second_field: 2 };
+//! assert_eq!(example[ExampleIndex::First], example.first);
+//! assert_eq!(example[ExampleIndex::SecondField], example.second_field);
+//! # }
+//! ```
+//!
+//! # Other index types
+//!
+//! Other primitive integer types can be used as indices by adding `#[named_array(index_type = T)]`,
+//! which may be given more than once.
+//! Negative indices are out of bounds, and panic like any other out of bounds index.
+//! Other types can be used in the same way if they implement `Into<usize>`, such as a newtype
+//! wrapping a `usize`, to keep indices into different structs apart.
+//!
+//! Once there is more than one integer index type, an unsuffixed literal such as `example[1]` can't
+//! tell which one it is, so it falls back to `i32` like any other ambiguous integer.
+//! With an `index_type` other than `i32`, such literals then fail to compile, and need a suffix, as in
+//! `example[1usize]`, or a typed variable instead.
+//!
+//! ```rust
+//! # use named_array::named_array;
+//! #[derive(named_array)]
+//! #[named_array(index_type = i32)]
+//! struct Example {
+//!     a: u32,
+//!     b: u32,
+//! }
+//! # fn main() {
+//! let example = Example { a: 1, b: 2 };
+//! let i: i32 = 1;
+//! assert_eq!(example[i], example.b);
+//! # }
+//! ```
+//!
+//! # Trait objects
+//!
+//! If the fields have different types which all implement a common trait, adding
+//! `#[named_array(dyn = Trait)]` views them as `dyn Trait` instead, and lifts the requirement that
+//! they all have the same type.
+//! Methods which move fields in or out of the struct, such as `map` and `swap`, are not generated in
+//! this case.
+//! The `as_dyn` method is generated as well, returning the field at an index as a trait object,
+//! like indexing does.
+//!
+//! ```rust
+//! # use named_array::named_array;
+//! # use std::fmt::Display;
+//! #[derive(named_array)]
+//! #[named_array(dyn = Display)]
+//! struct Example {
+//!     a: u32,
+//!     b: &'static str,
+//! }
+//! # fn main() {
+//! let example = Example { a: 1, b: "two" };
+//! assert_eq!(example[0].to_string(), "1");
+//! assert_eq!(example[1].to_string(), "two");
+//! # }
+//! ```
+//!
+//! # Read-only structs
+//!
+//! Adding `#[named_array(readonly)]` generates only the parts of the array view which don't mutate
+//! the fields, such as [`Index`], `get` and `iter`, leaving out [`IndexMut`], `get_mut`, `swap` and so
+//! on.
+//! The fields can still be mutated directly.
+//!
+//! # Structs which implement `Drop`
+//!
+//! Methods which move fields out of the struct, such as `map` and `into_iter`, and the conversion
+//! into an array, don't compile for a struct which implements `Drop`, unless its fields are `Copy`.
+//! Adding `#[named_array(no_move)]` leaves them out, keeping everything which only borrows the fields
+//! or builds a new struct, such as `swap`, `cloned` and the conversion from an array.
+//! This can't be combined with `arith`, since the operators move the fields too.
+//!
+//! # Other traits
+//!
+//! Adding `#[named_array(default)]` implements [`Default`], by setting every field (including skipped
+//! ones) to its default value.
+//! This is opt-in, so that it doesn't conflict with `#[derive(Default)]` or a manual impl.
+//!
+//! Similarly, `#[named_array(eq)]` implements [`PartialEq`] by comparing the fields in index order,
+//! stopping at the first difference, like comparing arrays would.
+//! Skipped fields are not compared.
+//! In the same way, `#[named_array(hash)]` implements [`Hash`] by hashing the fields in index
+//! order, so that it agrees with `eq` even when the fields are reordered.
+//!
+//! For numeric fields, `#[named_array(arith)]` implements [`Add`], [`Sub`], [`Mul`] and [`Div`]
+//! element-wise, so that the struct can be used as a small vector.
+//! They are also implemented with a single element on the right hand side, which is applied to
+//! every field, such as to scale the vector.
+//! Skipped fields are taken from the left hand side.
+//!
+//! If the fields are [`Ord`], `#[named_array(ord)]` implements [`PartialOrd`] and [`Ord`] by
+//! comparing the fields lexicographically in index order, like arrays are compared.
+//! The struct must also implement [`Eq`], such as with `eq` and `#[derive(Eq)]`.
+//!
+//! `#[named_array(debug)]` implements [`Debug`] by formatting the fields as a list, in index order,
+//! so that the struct prints like an array, as in `[1, 2, 3]`.
+//!
+//! # Serde
+//!
+//! With the `serde` feature enabled, `#[named_array(serde)]` implements `Serialize` and `Deserialize`
+//! by treating the fields as a sequence of length `LEN`, in index order, so that the struct is
+//! represented like an array of its fields, such as `[1, 2, 3]` in JSON.
+//! Deserializing fails if the sequence has a different length, and skipped fields are set to their
+//! default value.
+//! The generated code refers to `::serde`, so the crate using it must depend on `serde` too.
+//!
+//! # Bytemuck
+//!
+//! With the `bytemuck` feature enabled, `#[named_array(pod)]` implements `bytemuck::Pod` and
+//! `bytemuck::Zeroable`, so that the struct can be cast to and from bytes without copying.
+//! The struct must be `#[repr(C)]` (or `#[repr(transparent)]`), since otherwise its layout isn't
+//! guaranteed, and every field (including skipped ones) must be `Pod`.
+//! The struct must also implement `Copy`, can't be generic, and can't have padding between its
+//! fields, which is checked when it is compiled.
+//! The generated code refers to `::bytemuck`, so the crate using it must depend on `bytemuck` too.
+//!
+//! # Attribute form
+//!
+//! The same code can also be generated with the `#[named_array::array]` attribute instead of the
+//! derive.
+//! Since an attribute and a derive can't share a name, it is called `array`.
+//! The options which would go in `#[named_array(...)]` on the struct are given as its arguments
+//! instead, while field options are written the same way as with the derive.
+//! It expands to the derive through the `::named_array` path, so if the crate is renamed in
+//! `Cargo.toml` or re-exported from another crate, the path to it has to be given with a
+//! `crate = path` argument, such as `#[my_crate::named_array::array(crate = my_crate::named_array)]`.
+//!
+//! ```rust
+//! #[named_array::array(index_type = u8)]
+//! struct Example {
+//!     a: u32,
+//!     #[named_array(skip)]
+//!     b: bool,
+//!     c: u32,
+//! }
+//! # fn main() {
+//! let example = Example { a: 1, b: true, c: 2 };
+//! assert_eq!(example[1u8], example.c);
+//! # }
+//! ```
+//!
+//! # `no_std`
+//!
+//! The generated code only refers to `core`, so it can be used in `#![no_std]` crates.
+//! The conversions enabled by the `alloc` feature only need `alloc`, rather than all of `std`.
+//!
 //! [`Index`]: ::core::ops::Index
 //! [`IndexMut`]: ::core::ops::IndexMut
+//! [`IntoIterator`]: ::core::iter::IntoIterator
+//! [`From`]: ::core::convert::From
+//! [`TryFrom`]: ::core::convert::TryFrom
+//! [`Copy`]: ::core::marker::Copy
+//! [`Clone`]: ::core::clone::Clone
+//! [`PartialEq`]: ::core::cmp::PartialEq
+//! [`Ord`]: ::core::cmp::Ord
+//! [`Default`]: ::core::default::Default
+//! [`Debug`]: ::core::fmt::Debug
+//! [`core::array::IntoIter<&T, N>`]: ::core::array::IntoIter
+//! [`Hash`]: ::core::hash::Hash
+//! [`Add`]: ::core::ops::Add
+//! [`Sub`]: ::core::ops::Sub
+//! [`Mul`]: ::core::ops::Mul
+//! [`Div`]: ::core::ops::Div
+//! [`PartialOrd`]: ::core::cmp::PartialOrd
+//! [`Eq`]: ::core::cmp::Eq
+//! [`AsRef<[T]>`]: ::core::convert::AsRef
+//! [`AsMut<[T]>`]: ::core::convert::AsMut
 
 use quote::quote;
+use syn::ext::IdentExt;
+
+#[cfg(doctest)]
+mod compile_fail;
+mod options;
+#[cfg(test)]
+mod tests;
+
+use options::{FieldOptions, Options};
 
 /// See the [crate] level documentation.
-#[proc_macro_derive(named_array)]
+#[proc_macro_derive(named_array, attributes(named_array))]
 pub fn named_array(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let source = syn::parse_macro_input!(input as syn::DeriveInput);
+    derive(&source).into()
+}
 
-    let (name, fields) = if let syn::Data::Struct(data) = source.data {
-        (source.ident, data.fields)
-    } else {
-        panic!("Only structs are supported");
+/// The attribute form of [`derive@named_array`], which takes the struct level options as
+/// arguments.
+///
+/// This expands to the derive through the `::named_array` path, unless another path is given with
+/// a `crate = path` argument, such as when the crate is renamed in `Cargo.toml` or re-exported.
+///
+/// See the [crate] level documentation.
+#[proc_macro_attribute]
+pub fn array(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+    let (krate, args) = match split_crate_arg(args.into()) {
+        Ok(split) => split,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let krate = krate.unwrap_or_else(|| syn::parse_quote!(::named_array));
+    // Attribute macros see fields before `#[cfg(...)]` is evaluated, unlike derives, so this
+    // defers to the derive rather than generating the code itself.
+    quote! {
+        #[derive(#krate::named_array)]
+        #[named_array(#( #args ),*)]
+        #input
+    }
+    .into()
+}
+
+/// Splits the arguments of [`array()`] at the top level commas, taking out the `crate = path`
+/// argument, if there is one.
+///
+/// The other arguments are passed on to the derive unparsed, since they aren't all valid [`syn::Meta`].
+fn split_crate_arg(
+    args: proc_macro2::TokenStream,
+) -> syn::Result<(Option<syn::Path>, Vec<proc_macro2::TokenStream>)> {
+    let mut krate = None;
+    let mut rest = Vec::new();
+    let mut tokens = args.into_iter().peekable();
+    while tokens.peek().is_some() {
+        let arg = tokens
+            .by_ref()
+            .take_while(
+                |token| !matches!(token, proc_macro2::TokenTree::Punct(p) if p.as_char() == ','),
+            )
+            .collect::<proc_macro2::TokenStream>();
+        let mut arg_tokens = arg.clone().into_iter();
+        match (arg_tokens.next(), arg_tokens.next()) {
+            (
+                Some(proc_macro2::TokenTree::Ident(ident)),
+                Some(proc_macro2::TokenTree::Punct(eq)),
+            ) if ident == "crate" && eq.as_char() == '=' => {
+                if krate.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        arg,
+                        "`crate` was given more than once",
+                    ));
+                }
+                krate = Some(syn::parse2(arg_tokens.collect())?);
+            }
+            _ if arg.is_empty() => {}
+            _ => rest.push(arg),
+        }
+    }
+    Ok((krate, rest))
+}
+
+fn derive(source: &syn::DeriveInput) -> proc_macro2::TokenStream {
+    let options = match Options::parse(&source.attrs) {
+        Ok(options) => options,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let syn::Data::Struct(data) = &source.data else {
+        return syn::Error::new_spanned(
+            &source.ident,
+            "named_array can only be derived for structs",
+        )
+        .to_compile_error();
     };
 
-    match fields {
-        syn::Fields::Named(fields) => make_named(name, fields),
-        syn::Fields::Unnamed(fields) => make_unnamed(name, fields),
-        _ => panic!("unit structs are not supported"),
+    match &data.fields {
+        syn::Fields::Named(fields) => make_named(source, fields, &options),
+        syn::Fields::Unnamed(fields) => make_unnamed(source, fields, &options),
+        syn::Fields::Unit => syn::Error::new_spanned(
+            &source.ident,
+            "named_array cannot be derived for unit structs",
+        )
+        .to_compile_error(),
     }
 }
 
-fn make_named(name: syn::Ident, fields: syn::FieldsNamed) -> proc_macro::TokenStream {
-    let mut fields = fields.named.iter();
+fn make_named(
+    source: &syn::DeriveInput,
+    fields: &syn::FieldsNamed,
+    options: &Options,
+) -> proc_macro2::TokenStream {
+    let fields = fields
+        .named
+        .iter()
+        .map(|f| (syn::Member::Named(f.ident.clone().unwrap()), f));
+    let array = match Array::new(source, fields, options) {
+        Ok(array) => array,
+        Err(tokens) => return tokens,
+    };
 
-    let mut errs = Vec::new();
-    let mut names = Vec::new();
-    let ty = fields
-        .next()
-        .map(|f| {
-            names.push(f.ident.as_ref().unwrap());
-            &f.ty
-        })
-        .expect("Expected at least one field");
-    for f in fields {
-        if f.ty != *ty {
-            errs.push(syn::Error::new_spanned(
-                &f.ty,
-                "All fields must have the same type",
-            ));
-        }
-        names.push(f.ident.as_ref().unwrap());
+    let mut tokens = make_impls(&array);
+    tokens.extend(make_index_enum(&array));
+    tokens
+}
+
+fn make_unnamed(
+    source: &syn::DeriveInput,
+    fields: &syn::FieldsUnnamed,
+    options: &Options,
+) -> proc_macro2::TokenStream {
+    if let Some(enum_name) = &options.index_enum {
+        return syn::Error::new_spanned(
+            enum_name,
+            "tuple structs don't have an index enum to rename",
+        )
+        .to_compile_error();
     }
 
-    if !errs.is_empty() {
-        let errs = errs.into_iter().map(|e| e.to_compile_error());
+    let fields = fields
+        .unnamed
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (syn::Member::Unnamed(syn::Index::from(i)), f));
+    let array = match Array::new(source, fields, options) {
+        Ok(array) => array,
+        Err(tokens) => return tokens,
+    };
+
+    make_impls(&array)
+}
 
-        // If there are any errors, return a dummy impl to avoid a flood of errors where indexing
-        // gets used.
-        return quote! {
-            #(#errs)*
+/// The struct being derived on, viewed as an array.
+struct Array<'a> {
+    name: &'a syn::Ident,
+    vis: &'a syn::Visibility,
+    generics: &'a syn::Generics,
+    options: &'a Options,
+    /// The type shared by all the elements.
+    ty: &'a syn::Type,
+    /// The fields which are part of the array view, in index order.
+    elements: Vec<Element<'a>>,
+    /// Every field of the struct, including skipped ones, in declaration order.
+    fields: Vec<Binding<'a>>,
+    /// Whether the struct is `#[non_exhaustive]`.
+    non_exhaustive: bool,
+}
 
-            impl ::core::ops::Index<usize> for #name {
-                type Output = #ty;
-                fn index(&self, _: usize) -> &Self::Output {
-                    unimplemented!("Unable to generate code due to previous errors");
+/// A field of the struct, along with the variable it is bound to when destructuring `self`.
+struct Binding<'a> {
+    member: syn::Member,
+    binding: syn::Ident,
+    ty: &'a syn::Type,
+}
+
+/// A field which is part of the array view.
+struct Element<'a> {
+    /// How the field is accessed.
+    member: syn::Member,
+    /// The variable the field is bound to when destructuring `self`.
+    binding: syn::Ident,
+    field: &'a syn::Field,
+    options: FieldOptions,
+}
+
+impl<'a> Array<'a> {
+    /// Checks the fields of the struct.
+    ///
+    /// `fields` pairs each field with the member used to access it, in declaration order.
+    /// If the fields are invalid, the errors are returned, ready to be emitted.
+    fn new(
+        source: &'a syn::DeriveInput,
+        fields: impl Iterator<Item = (syn::Member, &'a syn::Field)>,
+        options: &'a Options,
+    ) -> Result<Self, proc_macro2::TokenStream> {
+        let mut errs = Vec::new();
+        let mut elements = Vec::new();
+        let mut bindings = Vec::new();
+        let mut ty = None;
+        for (i, (member, f)) in fields.enumerate() {
+            let binding = quote::format_ident!("field_{i}");
+            // The compiler rejects this too, but a buggy macro can still produce it, and the
+            // generated code would then add confusing errors of its own.
+            if let syn::Member::Named(ident) = &member {
+                let duplicate = bindings.iter().any(|binding: &Binding| {
+                    matches!(&binding.member, syn::Member::Named(other) if other.unraw() == ident.unraw())
+                });
+                if duplicate {
+                    errs.push(syn::Error::new_spanned(
+                        ident,
+                        format!("field `{}` is declared more than once", ident.unraw()),
+                    ));
                 }
             }
+            bindings.push(Binding {
+                member: member.clone(),
+                binding: binding.clone(),
+                ty: &f.ty,
+            });
 
-            impl ::core::ops::IndexMut<usize> for #name {
-                fn index_mut(&mut self, _: usize) -> &mut Self::Output {
-                    unimplemented!("Unable to generate code due to previous errors");
+            let field_options = FieldOptions::parse(&f.attrs).map_err(|e| e.to_compile_error())?;
+            if field_options.skip {
+                if options.contiguous {
+                    errs.push(syn::Error::new_spanned(
+                        f,
+                        "fields cannot be skipped in a `contiguous` struct",
+                    ));
                 }
+                if let Some((_, span)) = field_options.index {
+                    errs.push(syn::Error::new(
+                        span,
+                        "skipped fields are not part of the array view, so they can't have an `index`",
+                    ));
+                }
+                continue;
             }
-        }
-        .into();
-    }
-
-    let len = names.len();
-    let panic_msg = format!("index out of bounds: the len is {len} but the index is {{}}");
-    let range1 = 0usize..;
-    let range2 = 0usize..;
 
-    quote! {
-        impl ::core::ops::Index<usize> for #name {
-            type Output = #ty;
-            fn index(&self, index: usize) -> &Self::Output {
-                match index {
-                    #(
-                        #range1 => &self.#names,
-                    )*
-                    i => panic!(#panic_msg, i),
+            match ty {
+                // With a trait object as the element type, the fields can have any type.
+                _ if options.dyn_trait.is_some() => {}
+                // The first field's type is used for all of them, so if they do differ the generated
+                // code fails to compile instead.
+                _ if options.unchecked_types && ty.is_some() => {}
+                // Likewise, when the element type is given, the fields are trusted to match it.
+                _ if options.output.is_some() => {}
+                None => ty = Some(&f.ty),
+                // Comparing the syntax trees ignores spacing, the same as comparing the tokens.
+                Some(ty) if f.ty != *ty => errs.push(syn::Error::new_spanned(
+                    &f.ty,
+                    format!(
+                        "all fields must have type `{}`, but this field is `{}`",
+                        display_tokens(ty),
+                        display_tokens(&f.ty),
+                    ),
+                )),
+                Some(_) => {}
+            }
+            if let Some((_, span)) = field_options.index {
+                if options.contiguous {
+                    errs.push(syn::Error::new(
+                        span,
+                        "fields cannot be reordered in a `contiguous` struct",
+                    ));
                 }
             }
+            elements.push(Element {
+                member,
+                binding,
+                field: f,
+                options: field_options,
+            });
         }
 
-        impl ::core::ops::IndexMut<usize> for #name {
-            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-            match index {
-                #(
-                    #range2 => &mut self.#names,
-                )*
-                i => panic!(#panic_msg, i),
-            }
+        if elements.is_empty() {
+            return Err(make_empty_error(&source.ident));
+        }
+        let ty = options
+            .dyn_trait
+            .as_ref()
+            .or(options.output.as_ref())
+            .or(ty)
+            .unwrap();
+        if options.flat && !matches!(ty, syn::Type::Array(_)) {
+            errs.push(syn::Error::new_spanned(
+                ty,
+                "`flat` requires the fields to be arrays",
+            ));
+        }
+        if options.newtype
+            && (elements.len() != 1 || !matches!(ty, syn::Type::Array(_) | syn::Type::Slice(_)))
+        {
+            errs.push(syn::Error::new_spanned(
+                &source.ident,
+                "`newtype` requires a single field, which is an array or slice",
+            ));
+        }
+
+        if let Some((chunk, span)) = options.chunk {
+            let len = elements.len();
+            if chunk == 0 || len % chunk != 0 {
+                errs.push(syn::Error::new(
+                    span,
+                    format!("{len} fields can't be split into chunks of {chunk}"),
+                ));
             }
         }
+
+        let elements = reorder(elements, &mut errs);
+
+        if !errs.is_empty() {
+            return Err(make_dummy(&source.ident, &source.generics, ty, errs));
+        }
+
+        Ok(Self {
+            name: &source.ident,
+            vis: &source.vis,
+            generics: &source.generics,
+            options,
+            ty,
+            elements,
+            fields: bindings,
+            non_exhaustive: source
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("non_exhaustive")),
+        })
     }
-    .into()
 }
 
-fn make_unnamed(name: syn::Ident, fields: syn::FieldsUnnamed) -> proc_macro::TokenStream {
-    let mut fields = fields.unnamed.iter();
+/// Puts the elements into index order, according to their `#[named_array(index = N)]` attributes.
+///
+/// If no field has an index, the declaration order is kept.
+fn reorder<'a>(elements: Vec<Element<'a>>, errs: &mut Vec<syn::Error>) -> Vec<Element<'a>> {
+    if elements
+        .iter()
+        .all(|element| element.options.index.is_none())
+    {
+        return elements;
+    }
 
-    let len = fields.len();
-    let mut errs = Vec::new();
-    let ty = fields
-        .next()
-        .map(|f| &f.ty)
-        .expect("Expected at least one field");
-    for f in fields {
-        if f.ty != *ty {
+    let len = elements.len();
+    let mut positions: Vec<_> = (0..len).map(|_| None).collect();
+    for element in elements {
+        let Some((index, span)) = element.options.index else {
             errs.push(syn::Error::new_spanned(
-                &f.ty,
-                "All fields must have the same type",
+                element.field,
+                "if any field has an `index`, all of them must",
             ));
+            continue;
+        };
+        // Since every field has an index, leaving a gap always means going out of range or
+        // repeating an index somewhere, so reporting those is enough.
+        match positions.get_mut(index) {
+            Some(position @ None) => *position = Some(element),
+            Some(Some(_)) => errs.push(syn::Error::new(
+                span,
+                format!("index {index} is given to more than one field"),
+            )),
+            None => errs.push(syn::Error::new(
+                span,
+                format!("index {index} is out of range, the indices must be 0..{len}"),
+            )),
         }
     }
 
-    if !errs.is_empty() {
-        let errs = errs.into_iter().map(|e| e.to_compile_error());
-        // If there are any errors, return a dummy impl to avoid a flood of errors where indexing
-        // gets used.
-        return quote! {
-            #(#errs)*
-            impl ::core::ops::Index<usize> for #name {
-                type Output = #ty;
-                fn index(&self, _: usize) -> &Self::Output {
-                    unimplemented!("Unable to generate code due to previous errors");
-                }
-            }
-            impl ::core::ops::IndexMut<usize> for #name {
-                fn index_mut(&mut self, _: usize) -> &mut Self::Output {
-                    unimplemented!("Unable to generate code due to previous errors");
+    positions.into_iter().flatten().collect()
+}
+
+impl Array<'_> {
+    /// Whether the elements have a known size, so they can be moved in and out of the struct.
+    fn is_sized(&self) -> bool {
+        self.options.dyn_trait.is_none()
+    }
+
+    /// Whether the struct can be built from its elements alone, by converting from an array or a
+    /// slice.
+    ///
+    /// Skipped fields have no element to come from, and `#[non_exhaustive]` structs shouldn't be
+    /// built outside of their crate, where the conversions could otherwise be used.
+    fn is_constructible(&self) -> bool {
+        self.fields.len() == self.elements.len() && !self.non_exhaustive
+    }
+
+    /// The generics of the struct, with each of `tys` required to implement `bound`.
+    fn bounded_generics<'t>(
+        &self,
+        tys: impl Iterator<Item = &'t syn::Type>,
+        bound: proc_macro2::TokenStream,
+    ) -> syn::Generics {
+        let mut generics = self.generics.clone();
+        let where_clause = generics.make_where_clause();
+        for ty in tys {
+            where_clause.predicates.push(syn::parse_quote!(#ty: #bound));
+        }
+        generics
+    }
+
+    /// A predicate requiring the elements to implement `bound`, for the where clause of a method
+    /// which is only available when they do.
+    ///
+    /// The bound is made higher-ranked so that it isn't rejected for being trivially false when
+    /// `#ty` is a concrete type which doesn't implement `bound`. The method can then only be
+    /// called with element types which do.
+    fn method_bound(&self, bound: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let ty = self.ty;
+        quote!(for<'__bound> #ty: #bound)
+    }
+
+    /// The members of the elements, in index order.
+    fn members(&self) -> Vec<&syn::Member> {
+        self.elements
+            .iter()
+            .map(|element| &element.member)
+            .collect()
+    }
+
+    /// The variables the elements are bound to when destructuring `self`, in index order.
+    fn bindings(&self) -> Vec<&syn::Ident> {
+        self.elements
+            .iter()
+            .map(|element| &element.binding)
+            .collect()
+    }
+
+    /// Expressions borrowing each element of `self` as a `&#ty`, in index order.
+    fn refs(&self) -> Vec<proc_macro2::TokenStream> {
+        self.borrows(quote!(&))
+    }
+
+    /// Expressions borrowing each element of `self` as a `&mut #ty`, in index order.
+    fn muts(&self) -> Vec<proc_macro2::TokenStream> {
+        self.borrows(quote!(&mut))
+    }
+
+    fn borrows(&self, borrow: proc_macro2::TokenStream) -> Vec<proc_macro2::TokenStream> {
+        let ty = self.ty;
+        self.elements
+            .iter()
+            .map(|element| {
+                let member = &element.member;
+                if self.is_sized() {
+                    quote!(#borrow self.#member)
+                } else {
+                    // Trait objects need an explicit coercion, since each field has its own type.
+                    quote!((#borrow self.#member as #borrow #ty))
                 }
-            }
+            })
+            .collect()
+    }
+}
+
+/// Generates the impls shared by named and tuple structs.
+///
+/// The generated functions are mostly small matches over the fields, so they are all marked
+/// `#[inline]` to allow them to be inlined across crates.
+fn make_impls(array: &Array) -> proc_macro2::TokenStream {
+    let mut tokens = make_index(array);
+    if let Some((expected, span)) = array.options.expect_len {
+        let len = array.elements.len();
+        let msg = format!("expected {expected} fields in the array view, but there are {len}");
+        tokens.extend(quote::quote_spanned! {span=>
+            const _: () = ::core::assert!(#len == #expected, #msg);
+        });
+    }
+    tokens.extend(make_accessors(array));
+    tokens.extend(make_iter(array));
+    if array.options.chunk.is_some() {
+        tokens.extend(make_chunks(array));
+    }
+    if array.is_sized() {
+        tokens.extend(make_by_value(array));
+        #[cfg(feature = "alloc")]
+        if !array.options.no_move {
+            tokens.extend(make_vec(array));
+        }
+        if array.is_constructible() {
+            tokens.extend(make_try_from_slice(array));
         }
-        .into();
+    } else {
+        tokens.extend(make_as_dyn(array));
+    }
+    if let (false, Some(error)) = (
+        array.is_sized() && array.is_constructible(),
+        &array.options.length_error,
+    ) {
+        tokens.extend(
+            syn::Error::new_spanned(
+                error,
+                "the struct can't be built from a slice, so there is no length error to rename",
+            )
+            .to_compile_error(),
+        );
+    }
+    if array.options.contiguous {
+        tokens.extend(make_slice(array));
+    }
+    if array.options.newtype {
+        tokens.extend(make_newtype(array));
+    }
+    tokens.extend(make_index_types(array));
+    if array.options.default {
+        tokens.extend(make_default(array));
+    }
+    if array.options.eq {
+        tokens.extend(make_eq(array));
+    }
+    if array.options.arith {
+        tokens.extend(make_arith(array));
+    }
+    if array.options.ord {
+        tokens.extend(make_ord(array));
+    }
+    if array.options.hash {
+        tokens.extend(make_hash(array));
+    }
+    if array.options.debug {
+        tokens.extend(make_debug(array));
+    }
+    #[cfg(feature = "serde")]
+    if array.options.serde {
+        tokens.extend(make_serde(array));
+    }
+    #[cfg(feature = "bytemuck")]
+    if array.options.pod {
+        tokens.extend(make_pod(array));
+    }
+    tokens
+}
+
+/// Returns the message used when an index is out of bounds.
+fn panic_msg(array: &Array) -> String {
+    if let Some(message) = &array.options.oob_message {
+        return message.clone();
     }
+    let len = array.elements.len();
+    format!("index out of bounds: the len is {len} but the index is {{}}")
+}
 
-    let panic_msg = format!("index out of bounds: the len is {len} but the index is {{}}");
-    let range1 = 0usize..len;
-    let range2 = 0usize..len;
-    let index1 = (0usize..len).map(syn::Index::from);
-    let index2 = (0usize..len).map(syn::Index::from);
+/// Generates the `Index<usize>` and `IndexMut<usize>` impls.
+///
+/// Each index is matched to a reference to its field, so that no references are built for the
+/// other fields. In release builds (checked on x86-64 with rustc 1.95), the match for a 16 field
+/// struct compiles to one bounds check followed by a jump table, and evenly spaced fields
+/// compile to arithmetic on the index, the same as indexing an array. Indexing into an array of
+/// references to the fields instead stores every reference on the stack before the bounds
+/// check, so the match is kept.
+fn make_index(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name, generics, ty, ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let refs = array.refs();
+    let muts = array.muts();
+    let indices = 0..array.elements.len();
+    let indices = &indices.collect::<Vec<_>>();
+    let panic_msg = panic_msg(array);
+    let len = array.elements.len();
+    let (attrs, index, fallback) = if array.options.wrapping {
+        (
+            // With a single field, the modulo is always 0.
+            quote!(#[allow(clippy::modulo_one)]),
+            quote!(index % #len),
+            quote!(_ => ::core::unreachable!()),
+        )
+    } else if array.options.saturating {
+        let last = len - 1;
+        (
+            quote!(),
+            quote!(::core::cmp::Ord::min(index, #last)),
+            quote!(_ => ::core::unreachable!()),
+        )
+    } else {
+        (
+            quote!(),
+            quote!(index),
+            quote!(i => ::core::panic!(#panic_msg, i)),
+        )
+    };
 
-    quote! {
-        impl ::core::ops::Index<usize> for #name {
+    let mut tokens = quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::Index<usize> for #name #ty_generics #where_clause {
             type Output = #ty;
+            // Out of bounds panics are reported at the indexing expression, rather than in here.
+            #[inline]
+            #[track_caller]
+            #attrs
             fn index(&self, index: usize) -> &Self::Output {
-                match index {
-                    #( #range1 => &self.#index1, )*
-                    i => panic!(#panic_msg, i),
+                match #index {
+                    #( #indices => #refs, )*
+                    #fallback
                 }
             }
         }
-        impl ::core::ops::IndexMut<usize> for #name {
-            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-                match index {
-                    #( #range2 => &mut self.#index2, )*
-                    i => panic!(#panic_msg, i),
+    };
+    if !array.options.readonly {
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::ops::IndexMut<usize> for #name #ty_generics #where_clause {
+                #[inline]
+                #[track_caller]
+                #attrs
+                fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                    match #index {
+                        #( #indices => #muts, )*
+                        #fallback
+                    }
                 }
             }
-        }
+        });
+    }
+    tokens
+}
+
+/// Generates the inherent methods which only borrow the elements.
+fn make_accessors(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name, generics, ty, ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let refs = &array.refs();
+    let muts = &array.muts();
+    let len = array.elements.len();
+    let indices = 0..len;
+    let indices = &indices.collect::<Vec<_>>();
+    let members = &array.members();
+    // There is always at least one element, so these can't fail.
+    let (first, first_mut) = (&refs[0], &muts[0]);
+    let (last, last_mut) = (&refs[len - 1], &muts[len - 1]);
+    let (split_first_rest, split_last_rest) = (&refs[1..], &refs[..len - 1]);
+    let rest_len = len - 1;
+    let rest_indices = &indices[1..];
+    let reversed = refs.iter().rev();
+    let names = array.elements.iter().map(|element| match &element.member {
+        syn::Member::Named(ident) => ident.unraw().to_string(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    });
+    let ord_bound = array.method_bound(quote!(::core::cmp::Ord));
+    // Formatting isn't available when panicking in const contexts.
+    let const_panic_msg = format!("index out of bounds: the len is {len}");
+
+    let mut tokens = quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The number of fields in the array view.
+            pub const LEN: usize = #len;
+
+            /// Returns the names of the fields, in index order.
+            ///
+            /// The fields of tuple structs are named by their positions, such as `"0"`.
+            #[inline]
+            pub const fn field_names() -> &'static [&'static str] {
+                &[#( #names, )*]
+            }
+
+            /// Returns the number of fields in the array view.
+            #[inline]
+            pub const fn len(&self) -> usize {
+                Self::LEN
+            }
+
+            /// Returns `true` if the array view has no fields.
+            #[inline]
+            pub const fn is_empty(&self) -> bool {
+                Self::LEN == 0
+            }
+
+            /// Returns `index`, clamped to the index of the last field, so that it is always in
+            /// bounds.
+            #[inline]
+            pub const fn clamp_index(&self, index: usize) -> usize {
+                if index < Self::LEN {
+                    index
+                } else {
+                    Self::LEN - 1
+                }
+            }
+
+            /// Returns a reference to the field at `index`, or `None` if out of bounds.
+            #[inline]
+            pub fn get(&self, index: usize) -> ::core::option::Option<&#ty> {
+                match index {
+                    #( #indices => ::core::option::Option::Some(#refs), )*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            /// Returns a reference to the field at `index`, or the index itself if it is out of
+            /// bounds.
+            #[inline]
+            pub fn try_index(&self, index: usize) -> ::core::result::Result<&#ty, usize> {
+                self.get(index).ok_or(index)
+            }
+
+            /// Returns a reference to the field at `index`, in a way which can be used in const
+            /// contexts.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is out of bounds.
+            #[inline]
+            #[track_caller]
+            pub const fn get_const(&self, index: usize) -> &#ty {
+                match index {
+                    #( #indices => #refs, )*
+                    _ => ::core::panic!(#const_panic_msg),
+                }
+            }
+
+            /// Returns a reference to the first field.
+            #[inline]
+            pub fn first(&self) -> &#ty {
+                #first
+            }
+
+            /// Returns a reference to the last field.
+            #[inline]
+            pub fn last(&self) -> &#ty {
+                #last
+            }
+
+            /// Returns a reference to the first field, along with references to the rest.
+            #[inline]
+            pub fn split_first(&self) -> (&#ty, [&#ty; #rest_len]) {
+                (#first, [#( #split_first_rest, )*])
+            }
+
+            /// Returns a reference to the last field, along with references to the rest.
+            #[inline]
+            pub fn split_last(&self) -> (&#ty, [&#ty; #rest_len]) {
+                (#last, [#( #split_last_rest, )*])
+            }
+
+            /// Returns references to each pair of adjacent fields, in index order.
+            #[inline]
+            pub fn pairs(&self) -> [(&#ty, &#ty); #rest_len] {
+                [#( (#split_last_rest, #split_first_rest), )*]
+            }
+
+            /// Pairs up references to the fields of `self` and `other`, in index order.
+            #[inline]
+            pub fn zip<'__named_array>(
+                &'__named_array self,
+                other: &'__named_array Self,
+            ) -> [(&'__named_array #ty, &'__named_array #ty); #len] {
+                [#( (&self.#members, &other.#members), )*]
+            }
+
+            /// Returns references to the fields, in reverse index order.
+            #[inline]
+            pub fn reversed(&self) -> [&#ty; #len] {
+                [#( #reversed, )*]
+            }
+
+            /// Returns the index of the first field which is equal to `needle`, or `None` if there
+            /// isn't one.
+            #[inline]
+            pub fn position<U: ?::core::marker::Sized>(&self, needle: &U) -> ::core::option::Option<usize>
+            where
+                #ty: ::core::cmp::PartialEq<U>,
+            {
+                #(
+                    if ::core::cmp::PartialEq::eq(#refs, needle) {
+                        return ::core::option::Option::Some(#indices);
+                    }
+                )*
+                ::core::option::Option::None
+            }
+
+            /// Returns a reference to the smallest field, or the first of them if there are
+            /// several.
+            #[inline]
+            pub fn min(&self) -> &#ty
+            where
+                #ord_bound,
+            {
+                let mut min = #first;
+                #(
+                    if ::core::cmp::Ord::cmp(#split_first_rest, min).is_lt() {
+                        min = #split_first_rest;
+                    }
+                )*
+                min
+            }
+
+            /// Returns a reference to the largest field, or the first of them if there are
+            /// several.
+            #[inline]
+            pub fn max(&self) -> &#ty
+            where
+                #ord_bound,
+            {
+                let mut max = #first;
+                #(
+                    if ::core::cmp::Ord::cmp(#split_first_rest, max).is_gt() {
+                        max = #split_first_rest;
+                    }
+                )*
+                max
+            }
+
+            /// Returns the index of the smallest field, or the lowest index if there are several.
+            #[inline]
+            pub fn position_min(&self) -> usize
+            where
+                #ord_bound,
+            {
+                let (mut position, mut min) = (0, #first);
+                #(
+                    if ::core::cmp::Ord::cmp(#split_first_rest, min).is_lt() {
+                        (position, min) = (#rest_indices, #split_first_rest);
+                    }
+                )*
+                position
+            }
+
+            /// Returns the index of the largest field, or the lowest index if there are several.
+            #[inline]
+            pub fn position_max(&self) -> usize
+            where
+                #ord_bound,
+            {
+                let (mut position, mut max) = (0, #first);
+                #(
+                    if ::core::cmp::Ord::cmp(#split_first_rest, max).is_gt() {
+                        (position, max) = (#rest_indices, #split_first_rest);
+                    }
+                )*
+                position
+            }
+
+            /// Returns `true` if any field is equal to `needle`.
+            #[inline]
+            pub fn contains<U: ?::core::marker::Sized>(&self, needle: &U) -> bool
+            where
+                #ty: ::core::cmp::PartialEq<U>,
+            {
+                #( ::core::cmp::PartialEq::eq(#refs, needle) )||*
+            }
+
+            /// Returns the number of fields for which `pred` returns `true`.
+            #[inline]
+            pub fn count_where(&self, mut pred: impl ::core::ops::FnMut(&#ty) -> bool) -> usize {
+                let mut count = 0;
+                #(
+                    if pred(#refs) {
+                        count += 1;
+                    }
+                )*
+                count
+            }
+        }
+    };
+    if !array.options.readonly {
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Returns a mutable reference to the field at `index`, or `None` if out of bounds.
+                #[inline]
+                pub fn get_mut(&mut self, index: usize) -> ::core::option::Option<&mut #ty> {
+                    match index {
+                        #( #indices => ::core::option::Option::Some(#muts), )*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+
+                /// Returns a mutable reference to the field at `index`, or the index itself if it is
+                /// out of bounds.
+                #[inline]
+                pub fn try_index_mut(&mut self, index: usize) -> ::core::result::Result<&mut #ty, usize> {
+                    self.get_mut(index).ok_or(index)
+                }
+
+                /// Returns mutable references to the fields at each of `indices`, or `None` if any of
+                /// them are out of bounds, or the same index is given twice.
+                #[inline]
+                pub fn get_many_mut<const K: usize>(
+                    &mut self,
+                    indices: [usize; K],
+                ) -> ::core::option::Option<[&mut #ty; K]> {
+                    for (k, &i) in indices.iter().enumerate() {
+                        if i >= #len || indices[..k].contains(&i) {
+                            return ::core::option::Option::None;
+                        }
+                    }
+                    // Taking more than one mutable reference through the match isn't possible, so
+                    // raw pointers to the fields are used instead.
+                    let ptrs: [*mut #ty; K] = ::core::array::from_fn(|k| match indices[k] {
+                        #( #indices => ::core::ptr::addr_of_mut!(self.#members) as *mut #ty, )*
+                        _ => ::core::unreachable!(),
+                    });
+                    // SAFETY: The indices were checked to be distinct, so each pointer is to a
+                    // different field of `self`, which is borrowed mutably for as long as the
+                    // references last.
+                    ::core::option::Option::Some(ptrs.map(|ptr| unsafe { &mut *ptr }))
+                }
+
+                /// Returns mutable references to the fields at `i` and `j`, or `None` if either is
+                /// out of bounds, or they are the same index.
+                #[inline]
+                pub fn two_mut(
+                    &mut self,
+                    i: usize,
+                    j: usize,
+                ) -> ::core::option::Option<(&mut #ty, &mut #ty)> {
+                    let [a, b] = self.get_many_mut([i, j])?;
+                    ::core::option::Option::Some((a, b))
+                }
+
+                /// Returns a mutable reference to the first field.
+                #[inline]
+                pub fn first_mut(&mut self) -> &mut #ty {
+                    #first_mut
+                }
+
+                /// Returns a mutable reference to the last field.
+                #[inline]
+                pub fn last_mut(&mut self) -> &mut #ty {
+                    #last_mut
+                }
+
+                /// Calls `f` with a mutable reference to each field, in index order.
+                #[inline]
+                pub fn for_each(&mut self, mut f: impl ::core::ops::FnMut(&mut #ty)) {
+                    #( f(#muts); )*
+                }
+            }
+        });
+    }
+    tokens
+}
+
+/// Generates the iterators over references to the elements.
+fn make_iter(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name, generics, ty, ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    // The impls of `IntoIterator` for references need an extra lifetime for the borrow.
+    let mut ref_generics = (*generics).clone();
+    ref_generics
+        .params
+        .insert(0, syn::parse_quote!('__named_array));
+    let (ref_impl_generics, ref_ty_generics, _) = ref_generics.split_for_impl();
+    // The same, with the lifetime elided, for the return types of `iter` and `iter_mut`.
+    let mut elided_generics = (*generics).clone();
+    elided_generics.params.insert(0, syn::parse_quote!('_));
+    let (_, elided_ty_generics, _) = elided_generics.split_for_impl();
+    let refs = &array.refs();
+    let muts = array.muts();
+    let len = array.elements.len();
+    let indices = 0..len;
+
+    let mut tokens = quote! {};
+    let (iter_ty, iter_ret, iter_expr) = if array.options.iter_types {
+        let iter = quote::format_ident!("{name}Iter");
+        tokens.extend(make_iter_type(
+            array,
+            &iter,
+            &ref_generics,
+            quote!(&'__named_array #ty),
+            &format!("An iterator over references to the fields of [`{name}`], in index order."),
+        ));
+        (
+            quote!(#iter #ref_ty_generics),
+            quote!(#iter #elided_ty_generics),
+            quote!(#iter {
+                inner: ::core::iter::IntoIterator::into_iter([#( #refs, )*]),
+                marker: ::core::marker::PhantomData,
+            }),
+        )
+    } else {
+        (
+            quote!(::core::array::IntoIter<&'__named_array #ty, #len>),
+            quote!(::core::array::IntoIter<&#ty, #len>),
+            quote!(::core::iter::IntoIterator::into_iter([#( #refs, )*])),
+        )
+    };
+
+    tokens.extend(quote! {
+        #[automatically_derived]
+        impl #ref_impl_generics ::core::iter::IntoIterator for &'__named_array #name #ty_generics #where_clause {
+            type Item = &'__named_array #ty;
+            type IntoIter = #iter_ty;
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                #iter_expr
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns an iterator over references to the fields, in index order.
+            #[inline]
+            pub fn iter(&self) -> #iter_ret {
+                ::core::iter::IntoIterator::into_iter(self)
+            }
+
+            /// Returns an iterator over the indices of the fields along with references to them, in
+            /// index order.
+            #[inline]
+            pub fn indexed_iter(&self) -> ::core::array::IntoIter<(usize, &#ty), #len> {
+                ::core::iter::IntoIterator::into_iter([#( (#indices, #refs), )*])
+            }
+        }
+    });
+    if !array.options.readonly {
+        let (iter_ty, iter_ret, iter_expr) = if array.options.iter_types {
+            let iter = quote::format_ident!("{name}IterMut");
+            tokens.extend(make_iter_type(
+                array,
+                &iter,
+                &ref_generics,
+                quote!(&'__named_array mut #ty),
+                &format!(
+                    "An iterator over mutable references to the fields of [`{name}`], in index order."
+                ),
+            ));
+            (
+                quote!(#iter #ref_ty_generics),
+                quote!(#iter #elided_ty_generics),
+                quote!(#iter {
+                    inner: ::core::iter::IntoIterator::into_iter([#( #muts, )*]),
+                    marker: ::core::marker::PhantomData,
+                }),
+            )
+        } else {
+            (
+                quote!(::core::array::IntoIter<&'__named_array mut #ty, #len>),
+                quote!(::core::array::IntoIter<&mut #ty, #len>),
+                quote!(::core::iter::IntoIterator::into_iter([#( #muts, )*])),
+            )
+        };
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #ref_impl_generics ::core::iter::IntoIterator for &'__named_array mut #name #ty_generics #where_clause {
+                type Item = &'__named_array mut #ty;
+                type IntoIter = #iter_ty;
+                #[inline]
+                fn into_iter(self) -> Self::IntoIter {
+                    #iter_expr
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Returns an iterator over mutable references to the fields, in index order.
+                #[inline]
+                pub fn iter_mut(&mut self) -> #iter_ret {
+                    ::core::iter::IntoIterator::into_iter(self)
+                }
+            }
+        });
+    }
+    tokens
+}
+
+/// Generates a named iterator type, for `#[named_array(iter_types)]`, which wraps the standard
+/// array iterator over `item`s.
+///
+/// The struct itself is used in a marker, so that every parameter of `generics` is used even when
+/// the elements don't mention it.
+fn make_iter_type(
+    array: &Array,
+    iter: &syn::Ident,
+    generics: &syn::Generics,
+    item: proc_macro2::TokenStream,
+    doc: &str,
+) -> proc_macro2::TokenStream {
+    let Array { name, vis, .. } = array;
+    let (_, name_ty_generics, _) = array.generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let len = array.elements.len();
+
+    quote! {
+        #[doc = #doc]
+        #vis struct #iter #impl_generics #where_clause {
+            inner: ::core::array::IntoIter<#item, #len>,
+            marker: ::core::marker::PhantomData<fn() -> #name #name_ty_generics>,
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::iter::Iterator for #iter #ty_generics #where_clause {
+            type Item = #item;
+            #[inline]
+            fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                self.inner.next()
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, ::core::option::Option<usize>) {
+                self.inner.size_hint()
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::iter::DoubleEndedIterator for #iter #ty_generics #where_clause {
+            #[inline]
+            fn next_back(&mut self) -> ::core::option::Option<Self::Item> {
+                self.inner.next_back()
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::iter::ExactSizeIterator for #iter #ty_generics #where_clause {
+            #[inline]
+            fn len(&self) -> usize {
+                self.inner.len()
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::iter::FusedIterator for #iter #ty_generics #where_clause {}
+    }
+}
+
+/// Generates the `chunks` method, for `#[named_array(chunk = N)]`.
+fn make_chunks(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name, generics, ty, ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (chunk, _) = array.options.chunk.unwrap();
+    let count = array.elements.len() / chunk;
+    let refs = array.refs();
+    let chunks = refs.chunks(chunk).map(|chunk| quote!([#( #chunk, )*]));
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns references to the fields, grouped into chunks of consecutive fields in index
+            /// order.
+            #[inline]
+            pub fn chunks(&self) -> [[&#ty; #chunk]; #count] {
+                [#( #chunks, )*]
+            }
+        }
+    }
+}
+
+/// Generates the `as_dyn` method, for structs with `dyn` elements.
+fn make_as_dyn(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name, generics, ty, ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let refs = array.refs();
+    let indices = 0..array.elements.len();
+    let panic_msg = panic_msg(array);
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the field at `index` as a trait object.
+            ///
+            /// This is the same as indexing, for call sites where a method reads more clearly.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is out of bounds.
+            #[inline]
+            #[track_caller]
+            pub fn as_dyn(&self, index: usize) -> &#ty {
+                match index {
+                    #( #indices => #refs, )*
+                    i => ::core::panic!(#panic_msg, i),
+                }
+            }
+        }
+    }
+}
+
+/// Generates the impls which move elements in and out of the struct.
+fn make_by_value(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name, generics, ty, ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let members = &array.members();
+    let bindings = &array.bindings();
+    let len = array.elements.len();
+    let indices = 0..len;
+    let indices = &indices.collect::<Vec<_>>();
+    let panic_msg = panic_msg(array);
+    let muts = array.muts();
+    let (last, init) = members.split_last().unwrap();
+    let (first, rest) = members.split_first().unwrap();
+    let clone_bound = array.method_bound(quote!(::core::clone::Clone));
+    let copy_bound = array.method_bound(quote!(::core::marker::Copy));
+    let sum_bound = array.method_bound(quote!(
+        ::core::clone::Clone + ::core::ops::Add<Output = #ty>
+    ));
+    let product_bound = array.method_bound(quote!(
+        ::core::clone::Clone + ::core::ops::Mul<Output = #ty>
+    ));
+    let dot_bound = array.method_bound(quote!(
+        ::core::clone::Clone + ::core::ops::Add<Output = #ty> + ::core::ops::Mul<Output = #ty>
+    ));
+
+    // Destructures `self` into all of its fields, to be put back together with `#construct`.
+    let destructure = {
+        let members = array.fields.iter().map(|field| &field.member);
+        let bindings = array.fields.iter().map(|field| &field.binding);
+        quote!(let Self { #( #members: #bindings, )* } = self;)
+    };
+    let construct = {
+        let members = array.fields.iter().map(|field| &field.member);
+        let bindings = array.fields.iter().map(|field| &field.binding);
+        quote!(Self { #( #members: #bindings, )* })
+    };
+
+    let from_array = if array.is_constructible() {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::From<[#ty; #len]> for #name #ty_generics #where_clause {
+                #[inline]
+                fn from(array: [#ty; #len]) -> Self {
+                    let [#( #bindings, )*] = array;
+                    #construct
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Structs which implement `Drop` can't have their fields moved out.
+    let moves = if array.options.no_move {
+        quote! {}
+    } else {
+        let (iter_ty, iter_expr, iter_type) = if array.options.iter_types {
+            let iter = quote::format_ident!("{name}IntoIter");
+            (
+                quote!(#iter #ty_generics),
+                quote!(#iter {
+                    inner: ::core::iter::IntoIterator::into_iter([#( self.#members, )*]),
+                    marker: ::core::marker::PhantomData,
+                }),
+                make_iter_type(
+                    array,
+                    &iter,
+                    generics,
+                    quote!(#ty),
+                    &format!(
+                        "An iterator over the fields of [`{name}`], by value, in index order."
+                    ),
+                ),
+            )
+        } else {
+            (
+                quote!(::core::array::IntoIter<#ty, #len>),
+                quote!(::core::iter::IntoIterator::into_iter([#( self.#members, )*])),
+                quote! {},
+            )
+        };
+        quote! {
+            #iter_type
+
+            #[automatically_derived]
+            impl #impl_generics ::core::iter::IntoIterator for #name #ty_generics #where_clause {
+                type Item = #ty;
+                type IntoIter = #iter_ty;
+                #[inline]
+                fn into_iter(self) -> Self::IntoIter {
+                    #iter_expr
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Applies `f` to each field, in index order, and returns the results as a new
+                /// struct.
+                ///
+                /// Any skipped fields are moved into the new struct unchanged.
+                #[inline]
+                pub fn map(self, mut f: impl ::core::ops::FnMut(#ty) -> #ty) -> Self {
+                    #destructure
+                    #( let #bindings = f(#bindings); )*
+                    #construct
+                }
+
+                /// Applies `f` to each field, in index order, and returns the results as a new
+                /// struct, or the first error returned by `f`.
+                ///
+                /// Once `f` returns an error, it isn't called on the remaining fields.
+                #[inline]
+                pub fn try_map<E>(
+                    self,
+                    mut f: impl ::core::ops::FnMut(#ty) -> ::core::result::Result<#ty, E>,
+                ) -> ::core::result::Result<Self, E> {
+                    #destructure
+                    #( let #bindings = f(#bindings)?; )*
+                    ::core::result::Result::Ok(#construct)
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::From<#name #ty_generics> for [#ty; #len] #where_clause {
+                #[inline]
+                fn from(value: #name #ty_generics) -> Self {
+                    [#( value.#members, )*]
+                }
+            }
+        }
+    };
+
+    let mut tokens = quote! {
+        #moves
+
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Adds all of the fields together, in index order.
+            #[inline]
+            pub fn sum(&self) -> #ty
+            where
+                #sum_bound,
+            {
+                // Calling `add` through the bound, rather than with `+`, avoids picking up any
+                // other impls, such as the one for references which returns a value. The fields
+                // are cloned, since they can't be moved out from behind `&self`.
+                let sum = ::core::clone::Clone::clone(&self.#first);
+                #( let sum = <#ty as ::core::ops::Add>::add(sum, ::core::clone::Clone::clone(&self.#rest)); )*
+                sum
+            }
+
+            /// Multiplies all of the fields together, in index order.
+            #[inline]
+            pub fn product(&self) -> #ty
+            where
+                #product_bound,
+            {
+                let product = ::core::clone::Clone::clone(&self.#first);
+                #( let product = <#ty as ::core::ops::Mul>::mul(product, ::core::clone::Clone::clone(&self.#rest)); )*
+                product
+            }
+
+            /// Returns the dot product of `self` and `other`, multiplying their fields pairwise and
+            /// adding up the results, in index order.
+            #[inline]
+            pub fn dot(&self, other: &Self) -> #ty
+            where
+                #dot_bound,
+            {
+                // See `sum` for why this uses `clone`.
+                let dot = <#ty as ::core::ops::Mul>::mul(
+                    ::core::clone::Clone::clone(&self.#first),
+                    ::core::clone::Clone::clone(&other.#first),
+                );
+                #(
+                    let dot = <#ty as ::core::ops::Add>::add(
+                        dot,
+                        <#ty as ::core::ops::Mul>::mul(
+                            ::core::clone::Clone::clone(&self.#rest),
+                            ::core::clone::Clone::clone(&other.#rest),
+                        ),
+                    );
+                )*
+                dot
+            }
+
+            /// Copies the fields into an array, in index order.
+            #[inline]
+            pub fn as_array(&self) -> [#ty; #len]
+            where
+                #copy_bound,
+            {
+                // The body is checked even where the bound can't hold, and reading a `&mut` field
+                // would then reborrow it rather than copy it, so the fields go through `clone`.
+                [#( ::core::clone::Clone::clone(&self.#members), )*]
+            }
+
+            /// Copies the fields into an array, in index order.
+            ///
+            /// This is the same as `as_array`, but mirrors [`Iterator::copied`].
+            #[inline]
+            pub fn copied(&self) -> [#ty; #len]
+            where
+                #copy_bound,
+            {
+                self.as_array()
+            }
+
+            /// Clones the fields into an array, in index order.
+            #[inline]
+            pub fn cloned(&self) -> [#ty; #len]
+            where
+                #clone_bound,
+            {
+                [#( ::core::clone::Clone::clone(&self.#members), )*]
+            }
+        }
+
+        #from_array
+    };
+    if !array.options.readonly {
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Sets every field to a clone of `value`, with the last field receiving `value`
+                /// itself.
+                #[inline]
+                pub fn fill(&mut self, value: #ty)
+                where
+                    #clone_bound,
+                {
+                    #( self.#init = ::core::clone::Clone::clone(&value); )*
+                    self.#last = value;
+                }
+
+                /// Rotates the fields in place, such that the field at index `n` moves to index 0, and
+                /// the first `n` fields move to the end.
+                ///
+                /// `n` is taken modulo the number of fields.
+                #[inline]
+                // With a single field, the modulo is always 0.
+                #[allow(clippy::modulo_one)]
+                pub fn rotate_left(&mut self, n: usize) {
+                    let n = n % Self::LEN;
+                    let mut fields = [#( #muts, )*];
+                    // Reversing both parts and then the whole only needs swaps, so the fields don't
+                    // need to be moved out of `self`.
+                    let reverse = |fields: &mut [&mut #ty]| {
+                        let len = fields.len();
+                        for i in 0..len / 2 {
+                            let (front, back) = fields.split_at_mut(len - 1 - i);
+                            ::core::mem::swap(&mut *front[i], &mut *back[0]);
+                        }
+                    };
+                    reverse(&mut fields[..n]);
+                    reverse(&mut fields[n..]);
+                    reverse(&mut fields);
+                }
+
+                /// Rotates the fields in place, such that the last `n` fields move to the start.
+                ///
+                /// `n` is taken modulo the number of fields.
+                #[inline]
+                #[allow(clippy::modulo_one)]
+                pub fn rotate_right(&mut self, n: usize) {
+                    self.rotate_left(Self::LEN - n % Self::LEN);
+                }
+
+                /// Replaces the field at `index` with `value`, returning the old value.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `index` is out of bounds.
+                #[inline]
+                #[track_caller]
+                pub fn replace(&mut self, index: usize, value: #ty) -> #ty {
+                    match index {
+                        #( #indices => ::core::mem::replace(&mut self.#members, value), )*
+                        i => ::core::panic!(#panic_msg, i),
+                    }
+                }
+
+                /// Swaps the fields at indices `i` and `j`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `i` or `j` are out of bounds.
+                #[inline]
+                #[track_caller]
+                pub fn swap(&mut self, i: usize, j: usize) {
+                    // Two mutable references can't be taken through the match at once, so raw pointers
+                    // to the fields are used instead. `ptr::swap` allows them to be the same.
+                    let a = match i {
+                        #( #indices => ::core::ptr::addr_of_mut!(self.#members), )*
+                        i => ::core::panic!(#panic_msg, i),
+                    };
+                    let b = match j {
+                        #( #indices => ::core::ptr::addr_of_mut!(self.#members), )*
+                        j => ::core::panic!(#panic_msg, j),
+                    };
+                    // SAFETY: Both pointers come from `self`, which is borrowed mutably for the
+                    // duration of the swap, so they are valid for reads and writes.
+                    unsafe { ::core::ptr::swap(a, b) };
+                }
+
+                /// Swaps the fields at indices `i` and `j`, or returns the first index which is out of
+                /// bounds without changing anything.
+                #[inline]
+                pub fn checked_swap(&mut self, i: usize, j: usize) -> ::core::result::Result<(), usize> {
+                    if i >= #len {
+                        return ::core::result::Result::Err(i);
+                    }
+                    if j >= #len {
+                        return ::core::result::Result::Err(j);
+                    }
+                    self.swap(i, j);
+                    ::core::result::Result::Ok(())
+                }
+            }
+        });
+    }
+    tokens
+}
+
+/// Generates the `Default` impl, for `#[named_array(default)]`.
+fn make_default(array: &Array) -> proc_macro2::TokenStream {
+    let name = array.name;
+    // Skipped fields are defaulted too, so every field's type needs to implement `Default`.
+    let generics = array.bounded_generics(
+        array.fields.iter().map(|field| field.ty),
+        quote!(::core::default::Default),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let members = array.fields.iter().map(|field| &field.member);
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::default::Default for #name #ty_generics #where_clause {
+            #[inline]
+            fn default() -> Self {
+                Self { #( #members: ::core::default::Default::default(), )* }
+            }
+        }
+    }
+}
+
+/// Generates the `PartialEq` impl, for `#[named_array(eq)]`.
+fn make_eq(array: &Array) -> proc_macro2::TokenStream {
+    let name = array.name;
+    let generics = array.bounded_generics(
+        array.elements.iter().map(|element| &element.field.ty),
+        quote!(::core::cmp::PartialEq),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let members = array.members();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::cmp::PartialEq for #name #ty_generics #where_clause {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                true #( && self.#members == other.#members )*
+            }
+        }
+    }
+}
+
+/// Generates the element-wise arithmetic operator impls, for `#[named_array(arith)]`.
+///
+/// Each operator is implemented both between two structs, and between a struct and a single
+/// element, which is applied to every field.
+fn make_arith(array: &Array) -> proc_macro2::TokenStream {
+    let Array { name, ty, .. } = array;
+    let ops = [
+        (quote!(Add), quote!(add)),
+        (quote!(Sub), quote!(sub)),
+        (quote!(Mul), quote!(mul)),
+        (quote!(Div), quote!(div)),
+    ];
+    // Skipped fields are taken from the left hand side.
+    let fields = |op, method, rhs: fn(&syn::Member) -> proc_macro2::TokenStream| {
+        array
+            .fields
+            .iter()
+            .map(|field| {
+                let member = &field.member;
+                if array
+                    .elements
+                    .iter()
+                    .any(|element| element.member == *member)
+                {
+                    let rhs = rhs(member);
+                    quote!(#member: <#ty as ::core::ops::#op>::#method(self.#member, #rhs))
+                } else {
+                    quote!(#member: self.#member)
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    ops.iter()
+        .map(|(op, method)| {
+            let generics = array.bounded_generics(
+                array.elements.iter().map(|element| &element.field.ty),
+                quote!(::core::ops::#op<Output = #ty>),
+            );
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            let elements = fields(op, method, |member| quote!(rhs.#member));
+            // The scalar is copied into each field.
+            let scalar_generics = array.bounded_generics(
+                array.elements.iter().map(|element| &element.field.ty),
+                quote!(::core::marker::Copy + ::core::ops::#op<Output = #ty>),
+            );
+            let (scalar_impl_generics, _, scalar_where_clause) = scalar_generics.split_for_impl();
+            let scalars = fields(op, method, |_| quote!(rhs));
+
+            quote! {
+                #[automatically_derived]
+                impl #impl_generics ::core::ops::#op for #name #ty_generics #where_clause {
+                    type Output = Self;
+                    #[inline]
+                    fn #method(self, rhs: Self) -> Self {
+                        Self { #( #elements, )* }
+                    }
+                }
+
+                #[automatically_derived]
+                impl #scalar_impl_generics ::core::ops::#op<#ty> for #name #ty_generics #scalar_where_clause {
+                    type Output = Self;
+                    #[inline]
+                    fn #method(self, rhs: #ty) -> Self {
+                        Self { #( #scalars, )* }
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+/// Generates the `PartialOrd` and `Ord` impls, for `#[named_array(ord)]`.
+fn make_ord(array: &Array) -> proc_macro2::TokenStream {
+    let name = array.name;
+    let generics = array.bounded_generics(
+        array.elements.iter().map(|element| &element.field.ty),
+        quote!(::core::cmp::Ord),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let members = array.members();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::cmp::PartialOrd for #name #ty_generics #where_clause {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::cmp::Ord for #name #ty_generics #where_clause {
+            #[inline]
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                #(
+                    match ::core::cmp::Ord::cmp(&self.#members, &other.#members) {
+                        ::core::cmp::Ordering::Equal => {}
+                        ordering => return ordering,
+                    }
+                )*
+                ::core::cmp::Ordering::Equal
+            }
+        }
+    }
+}
+
+/// Generates the `Hash` impl, for `#[named_array(hash)]`.
+fn make_hash(array: &Array) -> proc_macro2::TokenStream {
+    let name = array.name;
+    let generics = array.bounded_generics(
+        array.elements.iter().map(|element| &element.field.ty),
+        quote!(::core::hash::Hash),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let refs = array.refs();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::hash::Hash for #name #ty_generics #where_clause {
+            #[inline]
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                // References hash the same as what they point to, so this is the same as hashing
+                // the array the struct converts into.
+                ::core::hash::Hash::hash(&[#( #refs, )*], state)
+            }
+        }
+    }
+}
+
+/// Generates the `Debug` impl, for `#[named_array(debug)]`.
+fn make_debug(array: &Array) -> proc_macro2::TokenStream {
+    let name = array.name;
+    let generics = array.bounded_generics(
+        array.elements.iter().map(|element| &element.field.ty),
+        quote!(::core::fmt::Debug),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let members = array.members();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::fmt::Debug for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_list()
+                    #( .entry(&self.#members) )*
+                    .finish()
+            }
+        }
+    }
+}
+
+/// Generates the `Serialize` and `Deserialize` impls, for `#[named_array(serde)]`.
+///
+/// The elements are (de)serialized as a tuple, the same way as an array of them, so that the struct
+/// is represented as a sequence of length `LEN`.
+#[cfg(feature = "serde")]
+fn make_serde(array: &Array) -> proc_macro2::TokenStream {
+    let Array { name, ty, .. } = array;
+    let members = &array.members();
+    let bindings = &array.bindings();
+    let len = array.elements.len();
+    let indices = 0..len;
+    let expecting = format!("an array of length {len}");
+
+    let ser_generics = array.bounded_generics(
+        array.elements.iter().map(|element| &element.field.ty),
+        quote!(::serde::Serialize),
+    );
+    let (ser_impl_generics, ty_generics, ser_where_clause) = ser_generics.split_for_impl();
+
+    // Skipped fields have no element to come from, so they are defaulted, like with
+    // `#[serde(skip)]`.
+    let mut de_generics = array.bounded_generics(
+        array.elements.iter().map(|element| &element.field.ty),
+        quote!(::serde::Deserialize<'__named_array>),
+    );
+    let skipped = array
+        .fields
+        .iter()
+        .filter(|field| {
+            !array
+                .elements
+                .iter()
+                .any(|element| element.member == field.member)
+        })
+        .collect::<Vec<_>>();
+    for field in &skipped {
+        let ty = field.ty;
+        de_generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#ty: ::core::default::Default));
+    }
+    de_generics
+        .params
+        .insert(0, syn::parse_quote!('__named_array));
+    let (visitor_impl_generics, _, visitor_where_clause) = array.generics.split_for_impl();
+    let (de_impl_generics, _, de_where_clause) = de_generics.split_for_impl();
+    let skipped_members = skipped.iter().map(|field| &field.member);
+
+    quote! {
+        #[automatically_derived]
+        impl #ser_impl_generics ::serde::Serialize for #name #ty_generics #ser_where_clause {
+            fn serialize<S: ::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                let mut tuple = ::serde::Serializer::serialize_tuple(serializer, #len)?;
+                #( ::serde::ser::SerializeTuple::serialize_element(&mut tuple, &self.#members)?; )*
+                ::serde::ser::SerializeTuple::end(tuple)
+            }
+        }
+
+        const _: () = {
+            struct __NamedArrayVisitor #visitor_impl_generics (
+                ::core::marker::PhantomData<fn() -> #name #ty_generics>,
+            ) #visitor_where_clause;
+
+            #[automatically_derived]
+            impl #de_impl_generics ::serde::de::Visitor<'__named_array> for __NamedArrayVisitor #ty_generics #de_where_clause {
+                type Value = #name #ty_generics;
+
+                fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(#expecting)
+                }
+
+                fn visit_seq<A: ::serde::de::SeqAccess<'__named_array>>(
+                    self,
+                    mut seq: A,
+                ) -> ::core::result::Result<Self::Value, A::Error> {
+                    #(
+                        let ::core::option::Option::Some(#bindings) = seq.next_element::<#ty>()? else {
+                            return ::core::result::Result::Err(
+                                ::serde::de::Error::invalid_length(#indices, &self),
+                            );
+                        };
+                    )*
+                    // Formats which don't check the length themselves could otherwise leave
+                    // extra elements behind.
+                    if seq.next_element::<::serde::de::IgnoredAny>()?.is_some() {
+                        return ::core::result::Result::Err(
+                            ::serde::de::Error::invalid_length(#len + 1, &self),
+                        );
+                    }
+                    ::core::result::Result::Ok(#name {
+                        #( #members: #bindings, )*
+                        #( #skipped_members: ::core::default::Default::default(), )*
+                    })
+                }
+            }
+
+            #[automatically_derived]
+            impl #de_impl_generics ::serde::Deserialize<'__named_array> for #name #ty_generics #de_where_clause {
+                fn deserialize<D: ::serde::Deserializer<'__named_array>>(
+                    deserializer: D,
+                ) -> ::core::result::Result<Self, D::Error> {
+                    ::serde::Deserializer::deserialize_tuple(
+                        deserializer,
+                        #len,
+                        __NamedArrayVisitor(::core::marker::PhantomData),
+                    )
+                }
+            }
+        };
+    }
+}
+
+/// Generates the `Pod` and `Zeroable` impls, for `#[named_array(pod)]`.
+///
+/// The `repr` is checked when parsing the options, but the padding can only be checked by the
+/// compiler, so an assertion is generated that the fields add up to the size of the struct.
+#[cfg(feature = "bytemuck")]
+fn make_pod(array: &Array) -> proc_macro2::TokenStream {
+    let Array { name, generics, .. } = array;
+    // The size of a generic struct can't be checked until it is instantiated.
+    if !generics.params.is_empty() {
+        return syn::Error::new_spanned(generics, "`pod` cannot be used with generic structs")
+            .to_compile_error();
+    }
+    // Skipped fields are part of the bytes too, so they also need to be `Pod`.
+    let tys = &array
+        .fields
+        .iter()
+        .map(|field| field.ty)
+        .collect::<Vec<_>>();
+    let msg = format!("`{name}` has padding between its fields, so it can't be `Pod`");
+
+    quote! {
+        const _: () = ::core::assert!(
+            ::core::mem::size_of::<#name>() == 0 #( + ::core::mem::size_of::<#tys>() )*,
+            #msg,
+        );
+
+        #[automatically_derived]
+        unsafe impl ::bytemuck::Zeroable for #name where #( #tys: ::bytemuck::Zeroable, )* {}
+
+        #[automatically_derived]
+        unsafe impl ::bytemuck::Pod for #name where #( #tys: ::bytemuck::Pod, )* {}
+    }
+}
+
+/// Generates the conversion into a `Vec`, with the `alloc` feature.
+#[cfg(feature = "alloc")]
+fn make_vec(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name, generics, ty, ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let len = array.elements.len();
+
+    quote! {
+        // `alloc` isn't in scope by default, even when `std` is, so it's brought in here instead.
+        const _: () = {
+            extern crate alloc;
+
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::From<#name #ty_generics> for alloc::vec::Vec<#ty> #where_clause {
+                #[inline]
+                fn from(value: #name #ty_generics) -> Self {
+                    alloc::vec::Vec::from(<[#ty; #len]>::from(value))
+                }
+            }
+        };
+    }
+}
+
+/// Generates the `TryFrom<&[T]>` impl, along with its error type.
+fn make_try_from_slice(array: &Array) -> proc_macro2::TokenStream {
+    let Array { name, vis, ty, .. } = array;
+    let mut generics = (*array.generics).clone();
+    generics.params.insert(0, syn::parse_quote!('__named_array));
+    let clone_bound = array.method_bound(quote!(::core::clone::Clone));
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(#clone_bound));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = array.generics.split_for_impl();
+    let members = array.members();
+    let bindings = &array.bindings();
+    let len = array.elements.len();
+    let error = match &array.options.length_error {
+        Some(error) => error.clone(),
+        None => quote::format_ident!("{name}LengthError"),
+    };
+    let error_doc = format!(
+        "The error returned when converting a slice into [`{name}`] fails, because its length isn't {len}."
+    );
+
+    quote! {
+        #[doc = #error_doc]
+        #[derive(
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::fmt::Debug,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+        )]
+        #vis struct #error {
+            /// The length which was expected.
+            pub expected: usize,
+            /// The length of the slice.
+            pub actual: usize,
+        }
+
+        #[automatically_derived]
+        impl ::core::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::write!(
+                    f,
+                    "expected a slice of length {}, but its length is {}",
+                    self.expected, self.actual,
+                )
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::TryFrom<&'__named_array [#ty]> for #name #ty_generics #where_clause {
+            type Error = #error;
+            #[inline]
+            fn try_from(slice: &'__named_array [#ty]) -> ::core::result::Result<Self, Self::Error> {
+                match slice {
+                    [#( #bindings, )*] => ::core::result::Result::Ok(Self {
+                        #( #members: ::core::clone::Clone::clone(#bindings), )*
+                    }),
+                    _ => ::core::result::Result::Err(#error {
+                        expected: #len,
+                        actual: slice.len(),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// Generates the slice view of a `contiguous` struct.
+fn make_slice(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name, generics, ty, ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let len = array.elements.len();
+
+    let (flat, flat_mut) = match ty {
+        syn::Type::Array(array_ty) if array.options.flat => {
+            let elem = &array_ty.elem;
+            let n = &array_ty.len;
+            // This is `as_flattened`, which is only available since Rust 1.80. The multiplication
+            // can only overflow for zero sized elements, which it panics for, like `as_flattened`.
+            let flat_len = quote! {
+                ::core::option::Option::expect(
+                    #len.checked_mul(#n),
+                    "the number of elements overflows `usize`",
+                )
+            };
+            (
+                quote! {
+                    #[automatically_derived]
+                    impl #impl_generics #name #ty_generics #where_clause {
+                        /// Returns the elements of all the fields as a single slice, in index order.
+                        #[inline]
+                        pub fn flat(&self) -> &[#elem] {
+                            let len = #flat_len;
+                            // SAFETY: The fields are laid out back to back, as for `as_slice`, and
+                            // so are the elements of each array, so there are `len` elements in a
+                            // row.
+                            unsafe {
+                                ::core::slice::from_raw_parts(
+                                    self.as_slice().as_ptr().cast::<#elem>(),
+                                    len,
+                                )
+                            }
+                        }
+                    }
+                },
+                quote! {
+                    #[automatically_derived]
+                    impl #impl_generics #name #ty_generics #where_clause {
+                        /// Returns the elements of all the fields as a single mutable slice, in index
+                        /// order.
+                        #[inline]
+                        pub fn flat_mut(&mut self) -> &mut [#elem] {
+                            let len = #flat_len;
+                            // SAFETY: See `flat`.
+                            unsafe {
+                                ::core::slice::from_raw_parts_mut(
+                                    self.as_mut_slice().as_mut_ptr().cast::<#elem>(),
+                                    len,
+                                )
+                            }
+                        }
+                    }
+                },
+            )
+        }
+        _ => Default::default(),
+    };
+
+    let mut tokens = quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the fields as a slice, in index order.
+            #[inline]
+            pub fn as_slice(&self) -> &[#ty] {
+                // SAFETY: `contiguous` is only accepted on `#[repr(C)]` and `#[repr(transparent)]`
+                // structs. Since every field has the same type, they are laid out back to back
+                // without padding, starting at offset 0.
+                unsafe { ::core::slice::from_raw_parts(self as *const Self as *const #ty, #len) }
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::AsRef<[#ty]> for #name #ty_generics #where_clause {
+            #[inline]
+            fn as_ref(&self) -> &[#ty] {
+                self.as_slice()
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::Index<::core::ops::Range<usize>> for #name #ty_generics #where_clause {
+            type Output = [#ty];
+            #[inline]
+            #[track_caller]
+            fn index(&self, range: ::core::ops::Range<usize>) -> &Self::Output {
+                &self.as_slice()[range]
+            }
+        }
+
+        #flat
+    };
+    if !array.options.readonly {
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Returns the fields as a mutable slice, in index order.
+                #[inline]
+                pub fn as_mut_slice(&mut self) -> &mut [#ty] {
+                    // SAFETY: See `as_slice`.
+                    unsafe { ::core::slice::from_raw_parts_mut(self as *mut Self as *mut #ty, #len) }
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::AsMut<[#ty]> for #name #ty_generics #where_clause {
+                #[inline]
+                fn as_mut(&mut self) -> &mut [#ty] {
+                    self.as_mut_slice()
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::core::ops::IndexMut<::core::ops::Range<usize>> for #name #ty_generics #where_clause {
+                #[inline]
+                #[track_caller]
+                fn index_mut(&mut self, range: ::core::ops::Range<usize>) -> &mut Self::Output {
+                    &mut self.as_mut_slice()[range]
+                }
+            }
+
+            #flat_mut
+        });
+    }
+    tokens
+}
+
+/// Generates the view of the elements of the single array or slice field, for
+/// `#[named_array(newtype)]`.
+fn make_newtype(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name, generics, ty, ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let elem = match ty {
+        syn::Type::Array(array_ty) => &array_ty.elem,
+        syn::Type::Slice(slice_ty) => &slice_ty.elem,
+        _ => unreachable!("`newtype` is checked to have an array or slice field"),
+    };
+    let member = &array.elements[0].member;
+
+    let mut tokens = quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the elements of the field as a slice.
+            #[inline]
+            pub fn as_slice(&self) -> &[#elem] {
+                &self.#member[..]
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::AsRef<[#elem]> for #name #ty_generics #where_clause {
+            #[inline]
+            fn as_ref(&self) -> &[#elem] {
+                self.as_slice()
+            }
+        }
+    };
+    if !array.options.readonly {
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Returns the elements of the field as a mutable slice.
+                #[inline]
+                pub fn as_mut_slice(&mut self) -> &mut [#elem] {
+                    &mut self.#member[..]
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::AsMut<[#elem]> for #name #ty_generics #where_clause {
+                #[inline]
+                fn as_mut(&mut self) -> &mut [#elem] {
+                    self.as_mut_slice()
+                }
+            }
+        });
+    }
+    tokens
+}
+
+/// Generates the impls of `Index` and `IndexMut` for the extra `index_type`s.
+fn make_index_types(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name,
+        generics,
+        options,
+        ty,
+        ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let index_types = &options.index_types;
+    let len = array.elements.len();
+    let panic_msg = panic_msg(array);
+    // When wrapping or saturating, indexing with the `usize` takes care of out of bounds indices.
+    let guard = (!options.wrapping && !options.saturating).then(|| quote!(if i < #len));
+    let last = len - 1;
+
+    // Converts `index` into a `usize`, and evaluates `#tokens` with it as `i`.
+    let convert = |index_type: &syn::Type, tokens: proc_macro2::TokenStream| {
+        if options::is_integer(index_type) {
+            let fallback = if options.saturating {
+                // Indices which don't fit in a `usize` are either negative, or past the end.
+                quote! {
+                    _ => {
+                        let i = if index < ::core::default::Default::default() { 0 } else { #last };
+                        #tokens
+                    }
+                }
+            } else {
+                quote!(_ => ::core::panic!(#panic_msg, index),)
+            };
+            quote! {
+                match <usize as ::core::convert::TryFrom<#index_type>>::try_from(index) {
+                    ::core::result::Result::Ok(i) #guard => #tokens,
+                    #fallback
+                }
+            }
+        } else {
+            // Wrappers may not implement `Display`, so the `usize` index reports any error instead.
+            quote! {{
+                let i: usize = ::core::convert::Into::into(index);
+                #tokens
+            }}
+        }
+    };
+    let refs = index_types
+        .iter()
+        .map(|index_type| convert(index_type, quote!(&self[i])));
+    let muts = index_types
+        .iter()
+        .map(|index_type| convert(index_type, quote!(&mut self[i])));
+
+    let mut tokens = quote! {
+        #(
+            #[automatically_derived]
+            impl #impl_generics ::core::ops::Index<#index_types> for #name #ty_generics #where_clause {
+                type Output = #ty;
+                #[inline]
+                #[track_caller]
+                fn index(&self, index: #index_types) -> &Self::Output {
+                    #refs
+                }
+            }
+        )*
+    };
+    if !options.readonly {
+        tokens.extend(quote! {
+            #(
+                #[automatically_derived]
+                impl #impl_generics ::core::ops::IndexMut<#index_types> for #name #ty_generics #where_clause {
+                    #[inline]
+                    #[track_caller]
+                    fn index_mut(&mut self, index: #index_types) -> &mut Self::Output {
+                        #muts
+                    }
+                }
+            )*
+        });
+    }
+    tokens
+}
+
+/// Generates an enum with a variant for each field of a named struct, to be used as an index.
+fn make_index_enum(array: &Array) -> proc_macro2::TokenStream {
+    let Array {
+        name,
+        vis,
+        generics,
+        ty,
+        ..
+    } = array;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let enum_name = match &array.options.index_enum {
+        Some(enum_name) => enum_name.clone(),
+        None => quote::format_ident!("{name}Index"),
+    };
+    let enum_doc = format!("The fields of [`{name}`], for use as an index.");
+
+    let refs = array.refs();
+    let muts = array.muts();
+    let mut variants = Vec::new();
+    let mut variant_docs = Vec::new();
+    let mut fields = Vec::new();
+    for member in array.members() {
+        let syn::Member::Named(ident) = member else {
+            unreachable!("Index enums are only generated for named structs");
+        };
+        let field = ident.unraw().to_string();
+        let variant = to_pascal_case(&field);
+        // Parsing rejects empty names, names starting with a digit, and keywords such as `Self`.
+        let error = if syn::parse_str::<syn::Ident>(&variant).is_err() {
+            Some(format!(
+                "the index enum variant for field `{field}` would be `{variant}`, which isn't a valid identifier"
+            ))
+        } else {
+            variants
+                .iter()
+                .position(|other| *other == variant)
+                .map(|other| {
+                    format!(
+                        "fields `{}` and `{field}` would both have the index enum variant `{variant}`",
+                        fields[other],
+                    )
+                })
+        };
+        if let Some(error) = error {
+            // The enum is only a convenience, so it is left out unless it was asked for by name.
+            return match &array.options.index_enum {
+                Some(_) => syn::Error::new_spanned(
+                    ident,
+                    format!("{error}, so the field must be renamed to use `rename_index_enum`"),
+                )
+                .to_compile_error(),
+                None => quote! {},
+            };
+        }
+        variants.push(quote::format_ident!("{}", variant, span = ident.span()));
+        variant_docs.push(format!("The `{field}` field."));
+        fields.push(field);
+    }
+    let variants = &variants;
+
+    let mut tokens = quote! {
+        #[doc = #enum_doc]
+        #[derive(
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::fmt::Debug,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+        )]
+        #[allow(dead_code)]
+        #vis enum #enum_name {
+            #(
+                #[doc = #variant_docs]
+                #variants,
+            )*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::Index<#enum_name> for #name #ty_generics #where_clause {
+            type Output = #ty;
+            #[inline]
+            fn index(&self, index: #enum_name) -> &Self::Output {
+                match index {
+                    #( #enum_name::#variants => #refs, )*
+                }
+            }
+        }
+    };
+    if !array.options.readonly {
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::ops::IndexMut<#enum_name> for #name #ty_generics #where_clause {
+                #[inline]
+                fn index_mut(&mut self, index: #enum_name) -> &mut Self::Output {
+                    match index {
+                        #( #enum_name::#variants => #muts, )*
+                    }
+                }
+            }
+        });
+    }
+    tokens
+}
+
+/// Converts a `snake_case` field name into a `PascalCase` variant name.
+fn to_pascal_case(field: &str) -> String {
+    field
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars)
+        })
+        .collect()
+}
+
+/// Renders tokens for use in an error message.
+///
+/// The `Display` impl of token streams puts spaces between all tokens, such as `Vec < u32 >`, so
+/// this only keeps the spaces which are needed to separate words.
+fn display_tokens(tokens: &impl quote::ToTokens) -> String {
+    use proc_macro2::{Delimiter, TokenTree};
+
+    fn write(tokens: proc_macro2::TokenStream, out: &mut String) {
+        let mut prev_word = false;
+        for token in tokens {
+            match token {
+                TokenTree::Group(group) => {
+                    let (open, close) = match group.delimiter() {
+                        Delimiter::Parenthesis => ("(", ")"),
+                        Delimiter::Bracket => ("[", "]"),
+                        Delimiter::Brace => ("{ ", " }"),
+                        Delimiter::None => ("", ""),
+                    };
+                    out.push_str(open);
+                    write(group.stream(), out);
+                    out.push_str(close);
+                    prev_word = false;
+                }
+                TokenTree::Punct(punct) => {
+                    match punct.as_char() {
+                        ',' | ';' => out.push_str(&format!("{punct} ")),
+                        '+' | '=' => out.push_str(&format!(" {punct} ")),
+                        _ => out.push(punct.as_char()),
+                    }
+                    prev_word = false;
+                }
+                TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                    if prev_word {
+                        out.push(' ');
+                    }
+                    out.push_str(&token.to_string());
+                    prev_word = true;
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    write(tokens.to_token_stream(), &mut out);
+    out.trim_end().to_owned()
+}
+
+/// Emits the error for a struct without any fields.
+///
+/// There is no element type to give a dummy impl, so this is only the error.
+fn make_empty_error(name: &syn::Ident) -> proc_macro2::TokenStream {
+    syn::Error::new_spanned(
+        name,
+        "named_array requires at least one field which is not skipped",
+    )
+    .to_compile_error()
+}
+
+/// Emits `errs` along with a dummy impl, to avoid a flood of errors where indexing gets used.
+fn make_dummy(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    ty: &syn::Type,
+    errs: Vec<syn::Error>,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let errs = errs.into_iter().map(|e| e.to_compile_error());
+
+    quote! {
+        #(#errs)*
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::Index<usize> for #name #ty_generics #where_clause {
+            type Output = #ty;
+            fn index(&self, _: usize) -> &Self::Output {
+                ::core::unimplemented!("Unable to generate code due to previous errors");
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::IndexMut<usize> for #name #ty_generics #where_clause {
+            fn index_mut(&mut self, _: usize) -> &mut Self::Output {
+                ::core::unimplemented!("Unable to generate code due to previous errors");
+            }
+        }
     }
-    .into()
 }
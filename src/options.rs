@@ -0,0 +1,425 @@
+//! Parsing of the `#[named_array(...)]` attributes placed on the struct and its fields.
+
+/// Options controlling code generation, given through `#[named_array(...)]` on the struct.
+#[derive(Default)]
+pub(crate) struct Options {
+    /// Set by `#[named_array(contiguous)]`, asserting that the fields can be viewed as a slice.
+    pub contiguous: bool,
+    /// Set by `#[named_array(flat)]`, for `contiguous` structs of arrays, to view the elements of
+    /// the arrays as a single slice.
+    pub flat: bool,
+    /// Set by `#[named_array(newtype)]`, for structs with a single array or slice field, to view
+    /// the elements of that field as a slice.
+    pub newtype: bool,
+    /// Set by `#[named_array(default)]`, to implement `Default` by defaulting every field.
+    pub default: bool,
+    /// Set by `#[named_array(eq)]`, to implement `PartialEq` by comparing the elements.
+    pub eq: bool,
+    /// Set by `#[named_array(arith)]`, to implement the arithmetic operators element-wise.
+    pub arith: bool,
+    /// Set by `#[named_array(ord)]`, to implement `PartialOrd` and `Ord` by comparing the elements
+    /// lexicographically.
+    pub ord: bool,
+    /// Set by `#[named_array(hash)]`, to implement `Hash` by hashing the elements in index order.
+    pub hash: bool,
+    /// Set by `#[named_array(debug)]`, to implement `Debug` by formatting the elements as a list.
+    pub debug: bool,
+    /// Set by `#[named_array(serde)]`, with the `serde` feature, to implement `Serialize` and
+    /// `Deserialize` by treating the elements as a sequence.
+    pub serde: bool,
+    /// Set by `#[named_array(pod)]`, with the `bytemuck` feature, to implement `Pod` and
+    /// `Zeroable` for a `#[repr(C)]` struct.
+    pub pod: bool,
+    /// Set by `#[named_array(oob_message = "...")]`, replacing the message used when an index is
+    /// out of bounds.
+    ///
+    /// This is a format string, with a single `{}` for the index.
+    pub oob_message: Option<String>,
+    /// Set by `#[named_array(wrapping)]`, to wrap out of bounds indices around instead of
+    /// panicking.
+    pub wrapping: bool,
+    /// Set by `#[named_array(saturating)]`, to clamp out of bounds indices to the nearest field
+    /// instead of panicking.
+    pub saturating: bool,
+    /// Set by `#[named_array(readonly)]`, to leave out everything which mutates the fields.
+    pub readonly: bool,
+    /// Set by `#[named_array(no_move)]`, to leave out everything which moves the fields out of the
+    /// struct, so that it can implement `Drop`.
+    pub no_move: bool,
+    /// Set by `#[named_array(iter_types)]`, to generate named iterator types, such as
+    /// `{Name}Iter`, instead of using the standard array iterator.
+    pub iter_types: bool,
+    /// Set by `#[named_array(unchecked_types)]`, to trust that the fields all have the same type,
+    /// even where they are written differently.
+    pub unchecked_types: bool,
+    /// Set by `#[named_array(output = T)]`, giving the element type instead of taking it from the
+    /// first field.
+    pub output: Option<syn::Type>,
+    /// Set by `#[named_array(expect_len = N)]`, asserting the number of fields in the array view.
+    pub expect_len: Option<(usize, proc_macro2::Span)>,
+    /// Set by `#[named_array(chunk = N)]`, to group the elements into chunks of `N`.
+    pub chunk: Option<(usize, proc_macro2::Span)>,
+    /// Set by `#[named_array(index_type = T)]`, for additional integer types, or wrappers around
+    /// `usize`, to index with.
+    pub index_types: Vec<syn::Type>,
+    /// Set by `#[named_array(dyn = Trait)]`, to view the fields as `dyn Trait`.
+    ///
+    /// This holds the full trait object type.
+    pub dyn_trait: Option<syn::Type>,
+    /// Set by `#[named_array(rename_index_enum = "Name")]`, to name the index enum.
+    pub index_enum: Option<syn::Ident>,
+    /// Set by `#[named_array(rename_length_error = "Name")]`, to name the error returned when
+    /// converting from a slice of the wrong length.
+    pub length_error: Option<syn::Ident>,
+}
+
+/// The names of the options which can be given on the struct.
+const OPTIONS: &[&str] = &[
+    "contiguous",
+    "chunk",
+    "default",
+    "expect_len",
+    "flat",
+    "newtype",
+    "eq",
+    "arith",
+    "ord",
+    "hash",
+    "debug",
+    "serde",
+    "pod",
+    "dyn",
+    "index_type",
+    "oob_message",
+    "output",
+    "readonly",
+    "no_move",
+    "iter_types",
+    "unchecked_types",
+    "wrapping",
+    "saturating",
+    "rename_index_enum",
+    "rename_length_error",
+];
+
+/// The names of the options which can be given on a field.
+const FIELD_OPTIONS: &[&str] = &["skip", "index"];
+
+/// Returns the error for an unknown `kind` of option, listing the `expected` ones.
+fn unknown_option(meta: &syn::meta::ParseNestedMeta, kind: &str, expected: &[&str]) -> syn::Error {
+    let expected = expected
+        .iter()
+        .map(|option| format!("`{option}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    meta.error(format!(
+        "unknown named_array {kind}, expected one of {expected}"
+    ))
+}
+
+impl Options {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        let repr = Repr::parse(attrs)?;
+        let mut flat_error = None;
+        let mut newtype_error = None;
+        let mut no_move_error = None;
+
+        for attr in attrs {
+            if !attr.path().is_ident("named_array") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("contiguous") {
+                    if !repr.c && !repr.transparent || repr.packed {
+                        return Err(meta.error(
+                            "`contiguous` requires the struct to be `#[repr(C)]` or `#[repr(transparent)]`",
+                        ));
+                    }
+                    options.contiguous = true;
+                    Ok(())
+                } else if meta.path.is_ident("chunk") {
+                    let lit: syn::LitInt = meta.value()?.parse()?;
+                    options.chunk = Some((lit.base10_parse()?, lit.span()));
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    options.default = true;
+                    Ok(())
+                } else if meta.path.is_ident("expect_len") {
+                    let lit: syn::LitInt = meta.value()?.parse()?;
+                    options.expect_len = Some((lit.base10_parse()?, lit.span()));
+                    Ok(())
+                } else if meta.path.is_ident("flat") {
+                    flat_error = Some(meta.error("`flat` requires `contiguous`"));
+                    options.flat = true;
+                    Ok(())
+                } else if meta.path.is_ident("newtype") {
+                    newtype_error = Some(meta.error("`newtype` cannot be used with `contiguous`"));
+                    options.newtype = true;
+                    Ok(())
+                } else if meta.path.is_ident("eq") {
+                    options.eq = true;
+                    Ok(())
+                } else if meta.path.is_ident("arith") {
+                    options.arith = true;
+                    Ok(())
+                } else if meta.path.is_ident("ord") {
+                    options.ord = true;
+                    Ok(())
+                } else if meta.path.is_ident("hash") {
+                    options.hash = true;
+                    Ok(())
+                } else if meta.path.is_ident("debug") {
+                    options.debug = true;
+                    Ok(())
+                } else if meta.path.is_ident("serde") {
+                    if !cfg!(feature = "serde") {
+                        return Err(meta.error(
+                            "`serde` requires the `serde` feature of named_array to be enabled",
+                        ));
+                    }
+                    options.serde = true;
+                    Ok(())
+                } else if meta.path.is_ident("pod") {
+                    if !cfg!(feature = "bytemuck") {
+                        return Err(meta.error(
+                            "`pod` requires the `bytemuck` feature of named_array to be enabled",
+                        ));
+                    }
+                    if !repr.c && !repr.transparent {
+                        return Err(meta.error(
+                            "`pod` requires the struct to be `#[repr(C)]` or `#[repr(transparent)]`",
+                        ));
+                    }
+                    options.pod = true;
+                    Ok(())
+                } else if meta.path.is_ident("dyn") {
+                    let path: syn::Path = meta.value()?.parse()?;
+                    options.dyn_trait = Some(syn::parse_quote!(dyn #path));
+                    Ok(())
+                } else if meta.path.is_ident("index_type") {
+                    let ty: syn::Type = meta.value()?.parse()?;
+                    if let syn::Type::Path(path) = &ty {
+                        if path.path.is_ident("usize") {
+                            return Err(syn::Error::new_spanned(
+                                ty,
+                                "`usize` can always be used as an index",
+                            ));
+                        }
+                    }
+                    if !is_integer(&ty) && !is_wrapper(&ty) {
+                        return Err(syn::Error::new_spanned(
+                            ty,
+                            "`index_type` must be a primitive integer type, or a type which implements `Into<usize>`",
+                        ));
+                    }
+                    options.index_types.push(ty);
+                    Ok(())
+                } else if meta.path.is_ident("oob_message") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    let message = lit.value();
+                    // Escaped braces aren't placeholders, and anything else can't be filled in.
+                    let placeholders = message.replace("{{", "").replace("}}", "");
+                    if placeholders.matches("{}").count() != 1
+                        || placeholders.matches(['{', '}']).count() != 2
+                    {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            "`oob_message` must contain exactly one `{}`, for the index",
+                        ));
+                    }
+                    options.oob_message = Some(message);
+                    Ok(())
+                } else if meta.path.is_ident("output") {
+                    options.output = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("readonly") {
+                    options.readonly = true;
+                    Ok(())
+                } else if meta.path.is_ident("no_move") {
+                    no_move_error = Some(meta.error(
+                        "`no_move` cannot be used with `arith`, since the operators move the fields",
+                    ));
+                    options.no_move = true;
+                    Ok(())
+                } else if meta.path.is_ident("iter_types") {
+                    options.iter_types = true;
+                    Ok(())
+                } else if meta.path.is_ident("unchecked_types") {
+                    options.unchecked_types = true;
+                    Ok(())
+                } else if meta.path.is_ident("wrapping") {
+                    if options.saturating {
+                        return Err(meta.error("`wrapping` cannot be used with `saturating`"));
+                    }
+                    options.wrapping = true;
+                    Ok(())
+                } else if meta.path.is_ident("saturating") {
+                    if options.wrapping {
+                        return Err(meta.error("`saturating` cannot be used with `wrapping`"));
+                    }
+                    options.saturating = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename_index_enum") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    let ident = lit.parse().map_err(|_| {
+                        syn::Error::new_spanned(&lit, "`rename_index_enum` must be an identifier")
+                    })?;
+                    options.index_enum = Some(ident);
+                    Ok(())
+                } else if meta.path.is_ident("rename_length_error") {
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    let ident = lit.parse().map_err(|_| {
+                        syn::Error::new_spanned(&lit, "`rename_length_error` must be an identifier")
+                    })?;
+                    options.length_error = Some(ident);
+                    Ok(())
+                } else {
+                    Err(unknown_option(&meta, "option", OPTIONS))
+                }
+            })?;
+        }
+
+        if let (false, Some(err)) = (options.contiguous, flat_error) {
+            return Err(err);
+        }
+        if let (true, Some(err)) = (options.contiguous, newtype_error) {
+            return Err(err);
+        }
+        if let (true, Some(dyn_trait)) = (options.contiguous, &options.dyn_trait) {
+            return Err(syn::Error::new_spanned(
+                dyn_trait,
+                "`dyn` cannot be used with `contiguous`",
+            ));
+        }
+        if let (true, Some(dyn_trait)) = (options.arith, &options.dyn_trait) {
+            return Err(syn::Error::new_spanned(
+                dyn_trait,
+                "`dyn` cannot be used with `arith`",
+            ));
+        }
+        if let (true, Some(err)) = (options.arith, no_move_error) {
+            return Err(err);
+        }
+        if let (true, Some(dyn_trait)) = (options.hash, &options.dyn_trait) {
+            return Err(syn::Error::new_spanned(
+                dyn_trait,
+                "`dyn` cannot be used with `hash`",
+            ));
+        }
+        if let (true, Some(dyn_trait)) = (options.serde, &options.dyn_trait) {
+            return Err(syn::Error::new_spanned(
+                dyn_trait,
+                "`dyn` cannot be used with `serde`",
+            ));
+        }
+        if let (Some(output), Some(_)) = (&options.output, &options.dyn_trait) {
+            return Err(syn::Error::new_spanned(
+                output,
+                "`output` cannot be used with `dyn`",
+            ));
+        }
+
+        Ok(options)
+    }
+}
+
+/// Whether `ty` could be a type wrapping a `usize` index, which can be converted with `Into<usize>`.
+///
+/// This only rules out the other primitive types, since the conversion can't be checked until
+/// the generated code is compiled.
+fn is_wrapper(ty: &syn::Type) -> bool {
+    const PRIMITIVES: [&str; 5] = ["bool", "char", "f32", "f64", "str"];
+    match ty {
+        syn::Type::Path(path) => !PRIMITIVES
+            .iter()
+            .any(|primitive| path.path.is_ident(primitive)),
+        _ => false,
+    }
+}
+
+/// Whether `ty` is one of the primitive integer types.
+pub(crate) fn is_integer(ty: &syn::Type) -> bool {
+    const INTEGERS: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            INTEGERS.iter().any(|integer| path.path.is_ident(integer))
+        }
+        _ => false,
+    }
+}
+
+/// Options given through `#[named_array(...)]` on a field.
+#[derive(Default)]
+pub(crate) struct FieldOptions {
+    /// Set by `#[named_array(skip)]`, leaving the field out of the array view.
+    pub skip: bool,
+    /// Set by `#[named_array(index = N)]`, giving the position of the field in the array view.
+    pub index: Option<(usize, proc_macro2::Span)>,
+}
+
+impl FieldOptions {
+    pub fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        for attr in attrs {
+            if !attr.path().is_ident("named_array") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    options.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("index") {
+                    let lit: syn::LitInt = meta.value()?.parse()?;
+                    options.index = Some((lit.base10_parse()?, lit.span()));
+                    Ok(())
+                } else {
+                    Err(unknown_option(&meta, "field option", FIELD_OPTIONS))
+                }
+            })?;
+        }
+
+        Ok(options)
+    }
+}
+
+/// The parts of a `#[repr(...)]` attribute which affect the layout of the fields.
+#[derive(Default)]
+struct Repr {
+    c: bool,
+    transparent: bool,
+    packed: bool,
+}
+
+impl Repr {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut repr = Self::default();
+
+        for attr in attrs {
+            if !attr.path().is_ident("repr") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("C") {
+                    repr.c = true;
+                } else if meta.path.is_ident("transparent") {
+                    repr.transparent = true;
+                } else if meta.path.is_ident("packed") {
+                    repr.packed = true;
+                }
+                // Skip over the arguments of things like `align(8)`.
+                if meta.input.peek(syn::token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    content.parse::<proc_macro2::TokenStream>()?;
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(repr)
+    }
+}
@@ -0,0 +1,371 @@
+//! Tests of the generated code which can't be checked from the integration tests, such as the
+//! text of error messages.
+
+use super::*;
+
+/// Runs the derive on `input`, and returns the generated code as a string.
+fn expand(input: proc_macro2::TokenStream) -> String {
+    derive(&syn::parse2(input).unwrap()).to_string()
+}
+
+#[test]
+fn mismatched_type_message() {
+    let output = expand(quote! {
+        struct Example {
+            a: u32,
+            b: i64,
+            c: Vec<u32>,
+        }
+    });
+    assert!(output.contains("all fields must have type `u32`, but this field is `i64`"));
+    assert!(output.contains("all fields must have type `u32`, but this field is `Vec<u32>`"));
+}
+
+#[test]
+fn unsupported_input_messages() {
+    let output = expand(quote! {
+        enum Example {
+            A(u32),
+            B(u32),
+        }
+    });
+    assert!(output.contains("named_array can only be derived for structs"));
+    let output = expand(quote! {
+        union Example {
+            a: u32,
+            b: u32,
+        }
+    });
+    assert!(output.contains("named_array can only be derived for structs"));
+    let output = expand(quote! {
+        struct Example;
+    });
+    assert!(output.contains("named_array cannot be derived for unit structs"));
+}
+
+#[test]
+fn duplicate_field_message() {
+    // This can only come from another macro, since the compiler rejects it otherwise.
+    let output = expand(quote! {
+        struct Example {
+            a: u32,
+            b: u32,
+            r#a: u32,
+        }
+    });
+    assert!(output.contains("field `a` is declared more than once"));
+    assert_eq!(output.matches("compile_error").count(), 1);
+}
+
+#[test]
+fn permutation_messages() {
+    let output = expand(quote! {
+        struct Example {
+            #[named_array(index = 1)]
+            a: u32,
+            #[named_array(index = 1)]
+            b: u32,
+            #[named_array(index = 3)]
+            c: u32,
+        }
+    });
+    assert!(output.contains("index 1 is given to more than one field"));
+    assert!(output.contains("index 3 is out of range, the indices must be 0..3"));
+}
+
+#[test]
+fn skipped_index_message() {
+    let output = expand(quote! {
+        struct Example {
+            a: u32,
+            #[named_array(skip, index = 7)]
+            b: u32,
+        }
+    });
+    assert!(output
+        .contains("skipped fields are not part of the array view, so they can't have an `index`"));
+}
+
+#[test]
+fn display_tokens() {
+    let ty: syn::Type = syn::parse_quote!(&'a mut core::option::Option<[u8; 4]>);
+    assert_eq!(
+        super::display_tokens(&ty),
+        "&'a mut core::option::Option<[u8; 4]>"
+    );
+    let ty: syn::Type = syn::parse_quote!(Box<dyn Fn(u32, u32) + Send>);
+    assert_eq!(super::display_tokens(&ty), "Box<dyn Fn(u32, u32) + Send>");
+}
+
+#[test]
+fn inline() {
+    let output = expand(quote! {
+        struct Example {
+            a: u32,
+            b: u32,
+        }
+    });
+    assert!(output.contains("# [inline] # [track_caller] fn index (& self , index : usize)"));
+    assert!(
+        output.contains("# [inline] # [track_caller] fn index_mut (& mut self , index : usize)")
+    );
+    assert!(output.contains("# [inline] pub fn get (& self , index : usize)"));
+    // Apart from `Display::fmt` on the error type, every function should be inlined.
+    assert_eq!(
+        output.matches(" fn ").count(),
+        output.matches("# [inline]").count() + 1
+    );
+}
+
+#[test]
+fn chunk_message() {
+    let output = expand(quote! {
+        #[named_array(chunk = 2)]
+        struct Example {
+            a: u32,
+            b: u32,
+            c: u32,
+        }
+    });
+    assert!(output.contains("3 fields can't be split into chunks of 2"));
+}
+
+#[test]
+fn automatically_derived() {
+    let output = expand(quote! {
+        #[named_array(contiguous, default, eq, hash, debug)]
+        #[repr(C)]
+        struct Example {
+            a: u32,
+            b: u32,
+        }
+    });
+    // Leave out the `impl Trait` argument types, which are the only other use of `impl`.
+    let impls = output.matches(" impl ").count() - output.matches(": impl ").count();
+    assert!(impls > 0);
+    assert_eq!(
+        output.matches("# [automatically_derived] impl ").count(),
+        impls
+    );
+}
+
+#[test]
+fn oob_message_placeholders() {
+    for message in ["{} and {}", "{index}", "{:?}", "none"] {
+        let output = expand(quote! {
+            #[named_array(oob_message = #message)]
+            struct Example {
+                a: u32,
+                b: u32,
+            }
+        });
+        assert!(
+            output.contains("must contain exactly one `{}`"),
+            "{message}"
+        );
+    }
+}
+
+#[test]
+fn unknown_option_messages() {
+    let output = expand(quote! {
+        #[named_array(readonly, read_only)]
+        struct Example {
+            a: u32,
+            b: u32,
+        }
+    });
+    assert!(output.contains("unknown named_array option, expected one of `contiguous`, `chunk`"));
+    assert!(output.contains("`readonly`"));
+
+    let output = expand(quote! {
+        struct Example {
+            #[named_array(skipped)]
+            a: u32,
+            b: u32,
+        }
+    });
+    assert!(output.contains("unknown named_array field option, expected one of `skip`, `index`"));
+}
+
+#[test]
+fn rename_length_error_messages() {
+    let output = expand(quote! {
+        #[named_array(rename_length_error = "1Error")]
+        struct Example(u32);
+    });
+    assert!(output.contains("`rename_length_error` must be an identifier"));
+
+    let output = expand(quote! {
+        #[named_array(rename_length_error = "Error")]
+        struct Example {
+            a: u32,
+            #[named_array(skip)]
+            b: u32,
+        }
+    });
+    assert!(output.contains("the struct can't be built from a slice"));
+    assert!(!output.contains("struct Error"));
+
+    let output = expand(quote! {
+        #[named_array(rename_length_error = "Error")]
+        struct Example(u32);
+    });
+    assert!(output.contains("struct Error"));
+    assert!(!output.contains("ExampleLengthError"));
+}
+
+#[test]
+fn qualified_derives() {
+    let output = expand(quote! {
+        struct Example {
+            a: u32,
+            b: u32,
+        }
+    });
+    assert!(!output.contains("derive (Clone"));
+    assert_eq!(
+        output.matches("derive (:: core :: clone :: Clone").count(),
+        2
+    );
+}
+
+#[test]
+fn qualified_unreachable() {
+    let output = expand(quote! {
+        struct Example {
+            a: u32,
+            b: u32,
+        }
+    });
+    assert!(output.contains("fn get_many_mut"));
+    assert!(output.contains("_ => :: core :: unreachable ! ()"));
+    assert!(!output.contains("_ => unreachable"));
+}
+
+#[test]
+fn crate_arg() {
+    let (krate, args) =
+        split_crate_arg(quote!(crate = ::renamed, index_type = u8, dyn = Debug,)).unwrap();
+    let krate = krate.unwrap();
+    assert_eq!(quote!(#krate).to_string(), quote!(::renamed).to_string());
+    let args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(
+        args,
+        [
+            quote!(index_type = u8).to_string(),
+            quote!(dyn = Debug).to_string()
+        ]
+    );
+
+    let (krate, args) = split_crate_arg(quote!()).unwrap();
+    assert!(krate.is_none());
+    assert!(args.is_empty());
+
+    let err = split_crate_arg(quote!(crate = a, crate = b)).unwrap_err();
+    assert_eq!(err.to_string(), "`crate` was given more than once");
+    assert!(split_crate_arg(quote!(crate = 1)).is_err());
+}
+
+#[test]
+fn invalid_index_enum_variants() {
+    let structs = [
+        quote!(
+            struct Example {
+                __: u32,
+                b: u32,
+            }
+        ),
+        quote!(
+            struct Example {
+                x: u32,
+                x_: u32,
+            }
+        ),
+        quote!(
+            struct Example {
+                self_: u32,
+                b: u32,
+            }
+        ),
+    ];
+    for input in structs {
+        let output = expand(input.clone());
+        assert!(!output.contains("compile_error"), "{input}");
+        assert!(!output.contains("enum ExampleIndex"), "{input}");
+        assert!(
+            output.contains("impl :: core :: ops :: Index < usize >"),
+            "{input}"
+        );
+    }
+
+    let renamed =
+        |fields| expand(quote!(#[named_array(rename_index_enum = "Field")] struct Example #fields));
+    let output = renamed(quote!({ __: u32, b: u32 }));
+    assert!(output.contains(
+        "the index enum variant for field `__` would be ``, which isn't a valid identifier"
+    ));
+    let output = renamed(quote!({ x: u32, x_: u32 }));
+    assert!(output.contains("fields `x` and `x_` would both have the index enum variant `X`"));
+    let output = renamed(quote!({ self_: u32, b: u32 }));
+    assert!(output.contains("the index enum variant for field `self_` would be `Self`"));
+}
+
+#[test]
+fn newtype_messages() {
+    let output = expand(quote! {
+        #[named_array(newtype)]
+        struct Example(u32);
+    });
+    assert!(output.contains("`newtype` requires a single field, which is an array or slice"));
+    let output = expand(quote! {
+        #[named_array(newtype)]
+        struct Example([u8; 4], [u8; 4]);
+    });
+    assert!(output.contains("`newtype` requires a single field, which is an array or slice"));
+    let output = expand(quote! {
+        #[named_array(contiguous, newtype)]
+        #[repr(C)]
+        struct Example([u8; 4]);
+    });
+    assert!(output.contains("`newtype` cannot be used with `contiguous`"));
+}
+
+#[test]
+fn no_move_arith_message() {
+    let output = expand(quote! {
+        #[named_array(arith, no_move)]
+        struct Example {
+            a: u32,
+            b: u32,
+        }
+    });
+    assert!(output.contains("`no_move` cannot be used with `arith`"));
+}
+
+#[test]
+fn dyn_hash_message() {
+    let output = expand(quote! {
+        #[named_array(dyn = Display, hash)]
+        struct Example {
+            a: u32,
+            b: &'static str,
+        }
+    });
+    assert!(output.contains("`dyn` cannot be used with `hash`"));
+}
+
+#[test]
+fn non_exhaustive_constructors() {
+    let output = expand(quote! {
+        #[non_exhaustive]
+        struct Example {
+            a: u32,
+            b: u32,
+        }
+    });
+    // Converting into an array is fine, but not back again.
+    assert!(output.contains("From < Example > for [u32 ; 2usize]"));
+    assert!(!output.contains("From < [u32 ; 2usize] >"));
+    assert!(!output.contains("TryFrom"));
+}
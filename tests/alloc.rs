@@ -0,0 +1,32 @@
+#![cfg(feature = "alloc")]
+
+use named_array::named_array;
+
+#[derive(named_array)]
+struct Arr {
+    #[named_array(index = 1)]
+    a: String,
+    #[named_array(index = 0)]
+    b: String,
+    #[named_array(skip)]
+    c: u32,
+}
+
+#[derive(named_array)]
+struct Tuple<T>(T, T, T);
+
+#[test]
+fn into_vec() {
+    let arr = Arr {
+        a: String::from("a"),
+        b: String::from("b"),
+        c: 0,
+    };
+    assert_eq!(arr.c, 0);
+    let vec = Vec::from(arr);
+    assert_eq!(vec.len(), 2);
+    assert_eq!(vec, ["b", "a"]);
+
+    let vec: Vec<u8> = Tuple(1, 2, 3).into();
+    assert_eq!(vec, [1, 2, 3]);
+}
@@ -0,0 +1,51 @@
+use named_array::named_array;
+
+#[derive(Clone, Copy, Debug, PartialEq, named_array)]
+#[named_array(arith)]
+struct Vec3 {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+#[derive(Debug, PartialEq, named_array)]
+#[named_array(arith)]
+struct Tagged<T>(T, T, #[named_array(skip)] &'static str);
+
+#[test]
+fn element_wise() {
+    let a = Vec3 {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    };
+    let b = Vec3::from([4.0, 5.0, 6.0]);
+    assert_eq!(a + b, Vec3::from([5.0, 7.0, 9.0]));
+    assert_eq!(a * b, Vec3::from([4.0, 10.0, 18.0]));
+    assert_eq!(b - a, Vec3::from([3.0, 3.0, 3.0]));
+    assert_eq!(b / a, Vec3::from([4.0, 2.5, 2.0]));
+}
+
+#[test]
+fn element_wise_skipped() {
+    let sum = Tagged(1, 2, "left") + Tagged(10, 20, "right");
+    assert_eq!(sum, Tagged(11, 22, "left"));
+}
+
+#[test]
+fn scalar() {
+    let v = Vec3::from([1.0, -2.0, 3.5]);
+    assert_eq!(v * 2.0, Vec3::from([2.0, -4.0, 7.0]));
+    assert_eq!(v + 1.0, Vec3::from([2.0, -1.0, 4.5]));
+    assert_eq!(v - 1.0, Vec3::from([0.0, -3.0, 2.5]));
+    assert_eq!(v / 2.0, Vec3::from([0.5, -1.0, 1.75]));
+    assert_eq!(Tagged(1, 2, "tag") * 3, Tagged(3, 6, "tag"));
+}
+
+#[test]
+fn dot() {
+    let a = Vec3::from([1.0, 2.0, 3.0]);
+    let b = Vec3::from([4.0, -5.0, 0.5]);
+    assert_eq!(a.dot(&b), -4.5);
+    assert_eq!(a.dot(&a), 14.0);
+}
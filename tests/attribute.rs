@@ -0,0 +1,56 @@
+#[named_array::array]
+#[derive(Debug, PartialEq)]
+struct Arr {
+    a: u32,
+    b: u32,
+    #[named_array(skip)]
+    c: bool,
+}
+
+#[named_array::array(contiguous, index_type = u8)]
+#[repr(C)]
+struct Tuple(u32, u32, u32);
+
+/// The crate re-exported under another name, which the attribute has to be told about.
+mod reexport {
+    pub use named_array as renamed;
+}
+
+#[reexport::renamed::array(readonly, crate = reexport::renamed)]
+struct Renamed {
+    a: u32,
+    b: u32,
+}
+
+#[test]
+fn named() {
+    let mut arr = Arr {
+        a: 1,
+        b: 2,
+        c: true,
+    };
+    assert_eq!(arr[1], 2);
+    arr[ArrIndex::A] = 3;
+    assert_eq!(Arr::LEN, 2);
+    assert_eq!(
+        arr,
+        Arr {
+            a: 3,
+            b: 2,
+            c: true
+        }
+    );
+}
+
+#[test]
+fn tuple() {
+    let tuple = Tuple(1, 2, 3);
+    assert_eq!(tuple[2u8], 3);
+    assert_eq!(tuple.as_slice(), [1, 2, 3]);
+}
+
+#[test]
+fn renamed() {
+    let renamed = Renamed { a: 1, b: 2 };
+    assert_eq!(renamed[RenamedIndex::B], 2);
+}
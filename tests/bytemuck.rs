@@ -0,0 +1,46 @@
+#![cfg(feature = "bytemuck")]
+
+use named_array::named_array;
+
+#[derive(Clone, Copy, Debug, PartialEq, named_array)]
+#[named_array(pod)]
+#[repr(C)]
+struct Arr {
+    a: u32,
+    b: u32,
+    #[named_array(skip)]
+    c: [u8; 4],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, named_array)]
+#[named_array(pod)]
+#[repr(C)]
+struct Tuple(u16, u16);
+
+#[test]
+fn to_bytes_and_back() {
+    let arr = Arr {
+        a: 1,
+        b: 2,
+        c: *b"skip",
+    };
+    let bytes: &[u8] = bytemuck::bytes_of(&arr);
+    assert_eq!(bytes.len(), 12);
+    assert_eq!(&bytes[..4], 1u32.to_ne_bytes());
+    assert_eq!(&bytes[8..], b"skip");
+    assert_eq!(bytemuck::pod_read_unaligned::<Arr>(bytes), arr);
+
+    let tuples = [Tuple(1, 2), Tuple(3, 4)];
+    let bytes: &[u8] = bytemuck::cast_slice(&tuples);
+    assert_eq!(bytes.len(), 8);
+    let back: &[Tuple] = bytemuck::cast_slice(bytes);
+    assert_eq!(back, tuples);
+    assert_eq!(back[1][0], 3);
+}
+
+#[test]
+fn zeroed() {
+    let arr: Arr = bytemuck::Zeroable::zeroed();
+    assert_eq!(arr.into_iter().collect::<Vec<_>>(), [0, 0]);
+    assert_eq!(arr.c, [0; 4]);
+}
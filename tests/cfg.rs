@@ -0,0 +1,35 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+struct Arr {
+    a: u32,
+    #[cfg(not(test))]
+    b: u32,
+    #[cfg(test)]
+    c: u32,
+}
+
+#[test]
+fn cfg() {
+    let arr = Arr { a: 1, c: 3 };
+    assert_eq!(Arr::LEN, 2);
+    assert_eq!(arr[1], 3);
+}
+
+#[named_array::array]
+struct Tuple(
+    #[cfg(not(test))] u32,
+    #[cfg(test)] u32,
+    #[cfg(not(test))]
+    #[named_array(skip)]
+    bool,
+    u32,
+);
+
+#[test]
+fn cfg_attribute() {
+    let tuple = Tuple(1, 2);
+    assert_eq!(Tuple::LEN, 2);
+    assert_eq!(tuple[0], 1);
+    assert_eq!(tuple.iter().sum::<u32>(), 3);
+}
@@ -0,0 +1,34 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(chunk = 3)]
+struct Matrix {
+    m00: f32,
+    m01: f32,
+    m02: f32,
+    m10: f32,
+    m11: f32,
+    m12: f32,
+}
+
+#[derive(named_array)]
+#[named_array(chunk = 1)]
+struct Tuple(u32, u32);
+
+#[test]
+fn chunks() {
+    let matrix = Matrix {
+        m00: 1.0,
+        m01: 2.0,
+        m02: 3.0,
+        m10: 4.0,
+        m11: 5.0,
+        m12: 6.0,
+    };
+    let [top, bottom] = matrix.chunks();
+    assert_eq!(top, [&1.0, &2.0, &3.0]);
+    assert_eq!(bottom, [&4.0, &5.0, &6.0]);
+    assert_eq!(*bottom[1], matrix.m11);
+
+    assert_eq!(Tuple(1, 2).chunks(), [[&1], [&2]]);
+}
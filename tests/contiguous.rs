@@ -0,0 +1,147 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(contiguous)]
+#[repr(C)]
+struct Arr {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+#[derive(named_array)]
+#[named_array(contiguous)]
+#[repr(C, align(16))]
+struct Tuple(u8, u8, u8);
+
+#[test]
+fn as_slice() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.as_slice(), &[1, 2, 3]);
+}
+
+#[test]
+fn as_mut_slice() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    arr.as_mut_slice()[1] = 4;
+    assert_eq!(arr.b, 4);
+}
+
+#[test]
+fn as_slice_tuple() {
+    let mut arr = Tuple(1, 2, 3);
+    arr.as_mut_slice().reverse();
+    assert_eq!(arr.as_slice(), &[3, 2, 1]);
+    assert_eq!(arr.0, 3);
+}
+
+fn sum(values: impl AsRef<[u32]>) -> u32 {
+    values.as_ref().iter().sum()
+}
+
+#[test]
+fn as_ref() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(sum(&arr), 6);
+    arr.as_mut()[0] = 4;
+    assert_eq!(sum(arr), 9);
+}
+
+#[test]
+fn index_range() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr[1..3], [2, 3]);
+    assert_eq!(arr[0..0], []);
+    arr[0..2].copy_from_slice(&[4, 5]);
+    assert_eq!(arr.a, 4);
+    assert_eq!(arr.b, 5);
+    assert_eq!(arr[0], 4);
+}
+
+#[test]
+#[should_panic(expected = "range end index 4 out of range for slice of length 3")]
+fn index_range_out_of_bounds() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let _ = &arr[1..4];
+}
+
+#[derive(named_array)]
+#[named_array(contiguous, flat)]
+#[repr(C)]
+struct Nested {
+    a: [u8; 4],
+    b: [u8; 4],
+}
+
+#[test]
+fn nested_arrays() {
+    let mut nested = Nested {
+        a: [1, 2, 3, 4],
+        b: [5, 6, 7, 8],
+    };
+    assert_eq!(nested[1], [5, 6, 7, 8]);
+    assert_eq!(nested[0][2], 3);
+    assert_eq!(nested.flat(), [1, 2, 3, 4, 5, 6, 7, 8]);
+    nested.flat_mut()[4] = 9;
+    assert_eq!(nested.b, [9, 6, 7, 8]);
+}
+
+/// The length of the arrays can be a const generic.
+#[derive(named_array)]
+#[named_array(contiguous, flat)]
+#[repr(C)]
+struct GenericNested<const N: usize> {
+    a: [u16; N],
+    b: [u16; N],
+    c: [u16; N],
+}
+
+#[test]
+fn generic_nested_arrays() {
+    let mut nested = GenericNested {
+        a: [1, 2],
+        b: [3, 4],
+        c: [5, 6],
+    };
+    assert_eq!(nested.flat(), [1, 2, 3, 4, 5, 6]);
+    nested.flat_mut()[5] = 7;
+    assert_eq!(nested.c, [5, 7]);
+
+    let empty = GenericNested::<0> {
+        a: [],
+        b: [],
+        c: [],
+    };
+    assert!(empty.flat().is_empty());
+}
+
+/// A newtype doesn't need a `repr` to view the elements of its field as a slice.
+#[derive(named_array)]
+#[named_array(newtype)]
+struct Buf([u8; 8]);
+
+#[test]
+fn newtype() {
+    let mut buf = Buf([1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(buf.as_slice(), [1, 2, 3, 4, 5, 6, 7, 8]);
+    buf.as_mut_slice()[7] = 9;
+    assert_eq!(buf.0[7], 9);
+    buf.as_mut()[0] = 0;
+    assert_eq!(buf.as_ref(), [0, 2, 3, 4, 5, 6, 7, 9]);
+    assert_eq!(buf[0], buf.0);
+}
+
+/// Without `newtype`, a single field struct can define its own slice view.
+#[derive(named_array)]
+struct Plain([u8; 4]);
+
+impl Plain {
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[test]
+fn plain_newtype() {
+    assert_eq!(Plain([1, 2, 3, 4]).as_slice(), [1, 2, 3, 4]);
+}
@@ -0,0 +1,100 @@
+use named_array::named_array;
+
+trait Shape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, factor: f64);
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.0 *= factor;
+    }
+}
+
+struct Rect(f64, f64);
+
+impl Shape for Rect {
+    fn area(&self) -> f64 {
+        self.0 * self.1
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.0 *= factor;
+        self.1 *= factor;
+    }
+}
+
+#[derive(named_array)]
+#[named_array(dyn = Shape)]
+struct Shapes {
+    square: Square,
+    rect: Rect,
+}
+
+#[derive(named_array)]
+#[named_array(dyn = core::fmt::Debug)]
+struct Tuple(u32, &'static str, bool);
+
+#[test]
+fn index_dyn() {
+    let mut shapes = Shapes {
+        square: Square(2.0),
+        rect: Rect(2.0, 3.0),
+    };
+    assert_eq!(shapes[0].area(), 4.0);
+    assert_eq!(shapes[1].area(), 6.0);
+    shapes[1].scale(2.0);
+    assert_eq!(shapes.rect.area(), 24.0);
+    assert_eq!(shapes[ShapesIndex::Square].area(), 4.0);
+}
+
+#[test]
+fn accessors_dyn() {
+    let mut shapes = Shapes {
+        square: Square(2.0),
+        rect: Rect(2.0, 3.0),
+    };
+    assert_eq!(shapes.get(1).map(|shape| shape.area()), Some(6.0));
+    assert!(shapes.get(2).is_none());
+    shapes.for_each(|shape| shape.scale(0.5));
+    assert_eq!(shapes.first().area(), 1.0);
+    assert_eq!(shapes.last().area(), 1.5);
+    assert_eq!(shapes.iter().map(|shape| shape.area()).sum::<f64>(), 2.5);
+}
+
+#[test]
+fn debug_dyn() {
+    let tuple = Tuple(1, "two", true);
+    let formatted: Vec<_> = tuple.iter().map(|x| format!("{x:?}")).collect();
+    assert_eq!(formatted, ["1", "\"two\"", "true"]);
+}
+
+#[test]
+fn get_many_mut_dyn() {
+    let mut shapes = Shapes {
+        square: Square(2.0),
+        rect: Rect(2.0, 3.0),
+    };
+    let [rect, square] = shapes.get_many_mut([1, 0]).unwrap();
+    rect.scale(2.0);
+    square.scale(3.0);
+    assert_eq!(shapes.rect.area(), 24.0);
+    assert_eq!(shapes.square.area(), 36.0);
+}
+
+#[test]
+fn as_dyn() {
+    let shapes = Shapes {
+        square: Square(2.0),
+        rect: Rect(2.0, 3.0),
+    };
+    assert_eq!(shapes.as_dyn(0).area(), 4.0);
+    assert_eq!(shapes.as_dyn(1).area(), 6.0);
+    assert_eq!(format!("{:?}", Tuple(1, "two", true).as_dyn(2)), "true");
+}
@@ -0,0 +1,238 @@
+use core::marker::PhantomData;
+
+use named_array::named_array;
+
+#[derive(named_array)]
+struct Wrap<T> {
+    a: T,
+    b: T,
+}
+
+#[derive(named_array)]
+struct Tuple<T>(T, T, T);
+
+#[derive(named_array)]
+struct Tagged<T, Tag> {
+    a: T,
+    b: T,
+    #[named_array(skip)]
+    tag: PhantomData<Tag>,
+}
+
+#[test]
+fn single_param() {
+    let mut wrap = Wrap { a: "a", b: "b" };
+    assert_eq!(wrap[0], "a");
+    wrap[1] = "c";
+    assert_eq!(wrap.b, "c");
+    assert_eq!(wrap.iter().copied().collect::<Vec<_>>(), ["a", "c"]);
+}
+
+#[test]
+fn single_param_tuple() {
+    let tuple = Tuple(1.0, 2.0, 3.0);
+    assert_eq!(tuple[2], 3.0);
+    assert_eq!(tuple.into_iter().sum::<f64>(), 6.0);
+}
+
+#[test]
+fn unused_param() {
+    struct Tag;
+
+    let tagged: Tagged<u32, Tag> = Tagged {
+        a: 1,
+        b: 2,
+        tag: PhantomData,
+    };
+    assert_eq!(tagged[0], 1);
+    assert_eq!(tagged[1], 2);
+    assert_eq!(Tagged::<u32, Tag>::LEN, 2);
+}
+
+#[derive(named_array)]
+struct Refs<'a> {
+    a: &'a u32,
+    b: &'a u32,
+}
+
+#[derive(named_array)]
+struct TwoLifetimes<'a, 'b>(&'a u32, &'a u32, #[named_array(skip)] PhantomData<&'b ()>);
+
+#[test]
+fn lifetime() {
+    let (x, y) = (1, 2);
+    let mut refs = Refs { a: &x, b: &y };
+    let first: &&u32 = &refs[0];
+    assert_eq!(**first, 1);
+    refs[0] = &y;
+    assert_eq!(*refs.a, 2);
+    assert_eq!((&refs).into_iter().map(|r| **r).sum::<u32>(), 4);
+}
+
+#[test]
+fn unused_lifetime() {
+    let (x, y) = (1, 2);
+    let two = TwoLifetimes(&x, &y, PhantomData);
+    let first: &u32 = two[0];
+    assert_eq!(*first, 1);
+    assert_eq!(*two[1], 2);
+}
+
+#[derive(named_array)]
+struct Bound<T>
+where
+    T: Clone,
+{
+    a: T,
+    b: T,
+}
+
+#[derive(named_array)]
+struct BoundTuple<T>(T, T)
+where
+    T: Copy + Default;
+
+#[test]
+fn where_clause() {
+    let mut bound = Bound {
+        a: String::from("a"),
+        b: String::from("b"),
+    };
+    assert_eq!(bound[1], "b");
+    bound[0].push('c');
+    assert_eq!(bound.a, "ac");
+
+    let tuple = BoundTuple(1u8, 2);
+    assert_eq!(tuple[0] + tuple[1], 3);
+}
+
+#[test]
+fn generic_from_array() {
+    let wrap = Wrap::from([1, 2]);
+    assert_eq!((wrap.a, wrap.b), (1, 2));
+    let array: [i32; 2] = wrap.into();
+    assert_eq!(array, [1, 2]);
+}
+
+#[derive(named_array)]
+struct Buf<const N: usize> {
+    a: [u8; N],
+    b: [u8; N],
+}
+
+#[test]
+fn const_param() {
+    let mut small = Buf { a: [1], b: [2] };
+    small[1][0] = 3;
+    assert_eq!(small.b, [3]);
+
+    let large = Buf {
+        a: [1, 2, 3, 4],
+        b: [5, 6, 7, 8],
+    };
+    assert_eq!(large[0], [1, 2, 3, 4]);
+    assert_eq!(large[1][3], 8);
+    assert_eq!(Buf::<4>::LEN, 2);
+}
+
+#[rustfmt::skip]
+#[derive(named_array)]
+struct Spaced {
+    a: Vec<u32>,
+    b: Vec< u32 >,
+    c: Vec <u32>,
+}
+
+#[test]
+fn spacing() {
+    let spaced = Spaced {
+        a: vec![1],
+        b: vec![2],
+        c: vec![3],
+    };
+    assert_eq!(spaced[1], [2]);
+    assert_eq!(spaced.len(), 3);
+}
+
+#[test]
+fn min_max_negative() {
+    let tuple = Tuple(-3i32, 5, -7);
+    assert_eq!(tuple.min(), &-7);
+    assert_eq!(tuple.max(), &5);
+}
+
+#[test]
+fn cloned() {
+    let wrap = Wrap {
+        a: String::from("a"),
+        b: String::from("b"),
+    };
+    assert_eq!(wrap.cloned(), ["a", "b"]);
+    assert_eq!(wrap.a, "a");
+}
+
+#[derive(named_array)]
+#[named_array(default, eq, debug)]
+struct Defaulted<T = u32, const N: usize = 2> {
+    a: [T; N],
+    b: [T; N],
+}
+
+#[test]
+fn generic_defaults() {
+    let mut defaulted: Defaulted = Defaulted::default();
+    defaulted[1] = [1, 2];
+    assert_eq!(defaulted.b, [1, 2]);
+    assert_eq!(defaulted, Defaulted::from([[0, 0], [1, 2]]));
+    assert_eq!(format!("{defaulted:?}"), "[[0, 0], [1, 2]]");
+    let strings: Defaulted<String, 1> = Defaulted::default();
+    assert_eq!(strings.len(), 2);
+}
+
+#[derive(named_array)]
+struct Pair<T> {
+    a: Box<T>,
+    b: Box<T>,
+}
+
+#[test]
+fn boxed_param() {
+    fn assert_output<A: core::ops::Index<usize, Output = O>, O>(_: &A) {}
+
+    let mut pair = Pair {
+        a: Box::new(1),
+        b: Box::new(2),
+    };
+    assert_output::<_, Box<i32>>(&pair);
+    assert_eq!(*pair[1], 2);
+    *pair[0] = 3;
+    assert_eq!(*pair.a, 3);
+    let [a, b]: [Box<i32>; 2] = pair.into();
+    assert_eq!((*a, *b), (3, 2));
+}
+
+#[derive(named_array)]
+struct MutRefs<'a> {
+    a: &'a mut u32,
+    b: &'a mut u32,
+}
+
+#[test]
+fn mut_lifetime() {
+    let (mut x, mut y) = (1, 2);
+    let mut refs = MutRefs {
+        a: &mut x,
+        b: &mut y,
+    };
+    let second: &mut &mut u32 = &mut refs[1];
+    **second += 10;
+    *refs[0] = 5;
+    refs.swap(0, 1);
+    assert_eq!(*refs.a, 12);
+    for r in refs.iter_mut() {
+        **r *= 2;
+    }
+    let [a, b]: [&mut u32; 2] = refs.into();
+    assert_eq!((*a, *b), (24, 10));
+    assert_eq!((x, y), (10, 24));
+}
@@ -0,0 +1,93 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+struct Arr {
+    a: u32,
+    second_field: u32,
+    #[named_array(skip)]
+    skipped: bool,
+    c: u32,
+}
+
+#[derive(named_array)]
+struct Reordered {
+    #[named_array(index = 1)]
+    a: u32,
+    #[named_array(index = 0)]
+    b: u32,
+}
+
+#[test]
+fn index_enum() {
+    let mut arr = Arr {
+        a: 1,
+        second_field: 2,
+        skipped: false,
+        c: 3,
+    };
+    assert_eq!(arr[ArrIndex::A], 1);
+    assert_eq!(arr[ArrIndex::SecondField], 2);
+    assert_eq!(arr[ArrIndex::C], 3);
+    arr[ArrIndex::SecondField] = 4;
+    assert_eq!(arr.second_field, 4);
+    assert_eq!(arr[1], 4);
+    assert!(!arr.skipped);
+}
+
+#[test]
+fn index_enum_derives() {
+    let index = ArrIndex::A;
+    let copy = index;
+    assert_eq!(index, copy);
+    assert_ne!(index, ArrIndex::C);
+    assert_eq!(format!("{index:?}"), "A");
+}
+
+#[test]
+fn index_enum_reordered() {
+    let arr = Reordered { a: 1, b: 2 };
+    assert_eq!(arr[ReorderedIndex::A], 1);
+    assert_eq!(arr[ReorderedIndex::B], 2);
+}
+
+#[allow(dead_code)]
+struct RenamedIndex;
+
+#[derive(named_array)]
+#[named_array(rename_index_enum = "Field")]
+struct Renamed {
+    a: u32,
+    b: u32,
+}
+
+#[test]
+fn renamed() {
+    let mut renamed = Renamed { a: 1, b: 2 };
+    assert_eq!(renamed[Field::B], 2);
+    renamed[Field::A] = 3;
+    assert_eq!(renamed.a, 3);
+}
+
+#[derive(named_array)]
+struct Raw {
+    r#type: u32,
+    r#match: u32,
+}
+
+#[test]
+fn raw_identifiers() {
+    let mut raw = Raw {
+        r#type: 1,
+        r#match: 2,
+    };
+    assert_eq!(raw[0], 1);
+    raw[1] = 3;
+    assert_eq!(raw.r#match, 3);
+    assert_eq!(raw[RawIndex::Type], 1);
+    assert_eq!(raw[RawIndex::Match], 3);
+}
+
+#[test]
+fn raw_field_names() {
+    assert_eq!(Raw::field_names(), ["type", "match"]);
+}
@@ -0,0 +1,92 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(index_type = i32)]
+struct Arr {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+#[derive(named_array)]
+#[named_array(index_type = i64, index_type = u8)]
+struct Tuple(u32, u32, u32);
+
+#[test]
+fn signed_index() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr[1i32], 2);
+    arr[2i32] = 4;
+    assert_eq!(arr.c, 4);
+    assert_eq!(arr[0usize], 1);
+}
+
+#[test]
+fn multiple_index_types() {
+    let arr = Tuple(1, 2, 3);
+    assert_eq!(arr[2i64], 3);
+    assert_eq!(arr[0u8], 1);
+    assert_eq!(arr[1usize], 2);
+}
+
+#[test]
+fn unsuffixed_literals() {
+    // With an `i32` index type, ambiguous literals fall back to it, so they still work.
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr[1], 2u32);
+    // Otherwise, they need a suffix, or a type from elsewhere.
+    let tuple = Tuple(1, 2, 3);
+    assert_eq!(tuple[1usize], 2);
+    let i: u8 = 2;
+    assert_eq!(tuple[i], 3);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is -1")]
+fn negative_index() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let _ = arr[-1];
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+fn signed_index_out_of_bounds() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let _ = arr[3i32];
+}
+
+mod index {
+    #[derive(Clone, Copy)]
+    pub struct Col(pub usize);
+
+    impl From<Col> for usize {
+        fn from(col: Col) -> Self {
+            col.0
+        }
+    }
+}
+
+#[derive(named_array)]
+#[named_array(index_type = index::Col, index_type = u8)]
+struct Row {
+    a: u32,
+    b: u32,
+}
+
+#[test]
+fn wrapper_index() {
+    use index::Col;
+
+    let mut row = Row { a: 1, b: 2 };
+    assert_eq!(row[Col(1)], 2);
+    row[Col(0)] = 3;
+    assert_eq!(row.a, 3);
+    assert_eq!(row[1u8], 2);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+fn wrapper_out_of_bounds() {
+    let row = Row { a: 1, b: 2 };
+    let _ = row[index::Col(2)];
+}
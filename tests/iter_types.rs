@@ -0,0 +1,61 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(iter_types)]
+struct Example {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+/// The generated types also carry the generics of the struct, even those the fields don't use.
+#[derive(named_array)]
+#[named_array(iter_types)]
+struct Generic<'a, T, M> {
+    x: &'a T,
+    y: &'a T,
+    #[named_array(skip)]
+    _marker: std::marker::PhantomData<M>,
+}
+
+/// The iterators can be stored in other types by name.
+struct Cursor<'a> {
+    iter: ExampleIter<'a>,
+}
+
+#[test]
+fn iter_types() {
+    let mut example = Example { a: 1, b: 2, c: 3 };
+    let it: ExampleIter = example.iter();
+    assert_eq!(it.len(), 3);
+    assert_eq!(it.rev().copied().collect::<Vec<_>>(), [3, 2, 1]);
+    {
+        let mut cursor = Cursor {
+            iter: (&example).into_iter(),
+        };
+        assert_eq!(cursor.iter.next(), Some(&1));
+        assert_eq!(cursor.iter.len(), 2);
+    }
+
+    let it: ExampleIterMut = example.iter_mut();
+    for x in it {
+        *x *= 10;
+    }
+
+    let it: ExampleIntoIter = example.into_iter();
+    assert_eq!(it.collect::<Vec<_>>(), [10, 20, 30]);
+}
+
+#[test]
+fn generic_iter_types() {
+    let (x, y) = (1, 2);
+    let generic = Generic::<i32, ()> {
+        x: &x,
+        y: &y,
+        _marker: std::marker::PhantomData,
+    };
+    let it: GenericIter<'_, '_, i32, ()> = generic.iter();
+    assert_eq!(it.copied().copied().sum::<i32>(), 3);
+    let it: GenericIntoIter<i32, ()> = generic.into_iter();
+    assert_eq!(it.copied().collect::<Vec<_>>(), [1, 2]);
+}
@@ -0,0 +1,62 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+struct Large {
+    f0: u64,
+    f1: u64,
+    f2: u64,
+    f3: u64,
+    f4: u64,
+    f5: u64,
+    f6: u64,
+    f7: u64,
+    f8: u64,
+    f9: u64,
+    f10: u64,
+    f11: u64,
+    f12: u64,
+    f13: u64,
+    f14: u64,
+    f15: u64,
+}
+
+fn large() -> Large {
+    Large {
+        f0: 0,
+        f1: 10,
+        f2: 20,
+        f3: 30,
+        f4: 40,
+        f5: 50,
+        f6: 60,
+        f7: 70,
+        f8: 80,
+        f9: 90,
+        f10: 100,
+        f11: 110,
+        f12: 120,
+        f13: 130,
+        f14: 140,
+        f15: 150,
+    }
+}
+
+#[test]
+fn every_index() {
+    let mut large = large();
+    assert_eq!(Large::LEN, 16);
+    for i in 0..16 {
+        assert_eq!(large[i], i as u64 * 10);
+        large[i] += 1;
+        assert_eq!(large[i], i as u64 * 10 + 1);
+    }
+    assert_eq!(large.f15, 151);
+    assert_eq!(large.get(16), None);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 16 but the index is 16")]
+fn out_of_bounds() {
+    let large = large();
+    let _ = large[16];
+}
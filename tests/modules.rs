@@ -0,0 +1,86 @@
+//! The generated types are emitted next to the struct, with the same visibility, so they can be
+//! used from elsewhere in the crate, regardless of the visibility of the fields.
+
+mod outer {
+    pub(crate) mod inner {
+        use named_array::named_array;
+
+        #[derive(named_array)]
+        pub(crate) struct Example {
+            pub(crate) a: u32,
+            pub(crate) b: u32,
+        }
+
+        #[derive(named_array)]
+        pub(super) struct Parent(pub(super) u32, pub(super) u32);
+
+        #[derive(Debug, named_array)]
+        #[named_array(eq)]
+        pub(crate) struct Mixed {
+            pub(crate) a: u32,
+            b: u32,
+            pub(super) c: u32,
+        }
+
+        impl Mixed {
+            pub(crate) fn b(&self) -> u32 {
+                self.b
+            }
+        }
+
+        #[derive(named_array)]
+        #[non_exhaustive]
+        pub(crate) struct NonExhaustive {
+            pub(crate) a: u32,
+            pub(crate) b: u32,
+        }
+    }
+
+    #[test]
+    fn parent_module() {
+        let parent = inner::Parent(1, 2);
+        assert_eq!(parent[1], 2);
+        assert!(inner::Parent::try_from(&[1][..]).is_err());
+    }
+}
+
+mod sibling {
+    use crate::outer::inner::{Example, ExampleIndex, ExampleLengthError};
+
+    #[test]
+    fn sibling_module() {
+        let mut example = Example { a: 1, b: 2 };
+        assert_eq!(example[ExampleIndex::B], 2);
+        example[ExampleIndex::A] = 3;
+        assert_eq!(example.a, 3);
+        let error: ExampleLengthError = Example::try_from(&[1, 2, 3][..]).err().unwrap();
+        assert_eq!((error.expected, error.actual), (2, 3));
+    }
+}
+
+/// The generated impls are emitted in the module of the struct, so they can construct it even
+/// where its fields are private.
+mod private_fields {
+    use crate::outer::inner::{Mixed, MixedIndex, NonExhaustive};
+
+    #[test]
+    fn mixed_visibility() {
+        let mut mixed = Mixed::from([1, 2, 3]);
+        assert_eq!((mixed.a, mixed.b()), (1, 2));
+        assert_eq!(mixed[1], 2);
+        mixed[MixedIndex::B] = 4;
+        assert_eq!(mixed.b(), 4);
+        assert_eq!(mixed.iter().copied().collect::<Vec<_>>(), [1, 4, 3]);
+
+        let mixed = mixed.map(|x| x * 10);
+        assert_eq!(mixed.b(), 40);
+        assert_eq!(mixed, Mixed::try_from(&[10, 40, 30][..]).unwrap());
+        assert_eq!(<[u32; 3]>::from(mixed), [10, 40, 30]);
+    }
+
+    #[test]
+    fn non_exhaustive() {
+        let arr = NonExhaustive { a: 1, b: 2 }.map(|x| x + 1);
+        assert_eq!((arr.a, arr.b), (2, 3));
+    }
+}
@@ -0,0 +1,31 @@
+use named_array::named_array;
+
+/// Fields can't be moved out of a struct which implements `Drop`.
+#[derive(named_array)]
+#[named_array(no_move)]
+struct Names {
+    first: String,
+    last: String,
+}
+
+impl Drop for Names {
+    fn drop(&mut self) {}
+}
+
+#[test]
+fn no_move() {
+    let mut names = Names {
+        first: String::from("a"),
+        last: String::from("b"),
+    };
+    assert_eq!(names[1], "b");
+    names.swap(0, 1);
+    assert_eq!(names.first, "b");
+    assert_eq!(
+        names.iter().map(String::as_str).collect::<Vec<_>>(),
+        ["b", "a"]
+    );
+    assert_eq!(names.cloned(), ["b", "a"]);
+    let names = Names::from([String::from("c"), String::from("d")]);
+    assert_eq!(names.last, "d");
+}
@@ -0,0 +1,36 @@
+//! The generated code only uses `core`, and `alloc` with the `alloc` feature, so it compiles
+//! without `std`.
+#![no_std]
+
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(contiguous, default, eq, hash, debug, index_type = u8, chunk = 2)]
+#[repr(C)]
+struct Arr {
+    a: u32,
+    b: u32,
+}
+
+#[derive(named_array)]
+#[named_array(dyn = core::fmt::Debug)]
+struct Dyn(u32, bool);
+
+#[test]
+fn no_std() {
+    let mut arr = Arr::from([1, 2]);
+    arr[1u8] = 3;
+    assert_eq!(arr.as_slice(), [1, 3]);
+    assert_eq!(arr, Arr::try_from(&[1, 3][..]).unwrap());
+    assert_eq!(arr.into_iter().sum::<u32>(), 4);
+    assert_eq!(Dyn(1, true).len(), 2);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn no_std_alloc() {
+    extern crate alloc;
+
+    let vec = alloc::vec::Vec::from(Arr { a: 1, b: 2 });
+    assert_eq!(vec, [1, 2]);
+}
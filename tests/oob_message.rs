@@ -0,0 +1,31 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(oob_message = "no channel {} in {{r, g, b}}", index_type = i32)]
+struct Rgb {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[test]
+#[should_panic = "no channel 3 in {r, g, b}"]
+fn oob_message() {
+    let rgb = Rgb { r: 1, g: 2, b: 3 };
+    assert_eq!(rgb[2usize], 3);
+    let _ = rgb[3usize];
+}
+
+#[test]
+#[should_panic = "no channel -1 in {r, g, b}"]
+fn oob_message_index_type() {
+    let mut rgb = Rgb { r: 1, g: 2, b: 3 };
+    rgb[-1i32] = 0;
+}
+
+#[test]
+#[should_panic = "no channel 4 in {r, g, b}"]
+fn oob_message_swap() {
+    let mut rgb = Rgb { r: 1, g: 2, b: 3 };
+    rgb.swap(0, 4);
+}
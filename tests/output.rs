@@ -0,0 +1,21 @@
+use named_array::named_array;
+
+type Id = u32;
+
+#[derive(named_array)]
+#[named_array(output = u32)]
+struct Ids {
+    a: Id,
+    b: u32,
+    c: core::primitive::u32,
+}
+
+#[test]
+fn output_type() {
+    let mut ids = Ids { a: 1, b: 2, c: 3 };
+    let first: &u32 = &ids[0];
+    assert_eq!(*first, 1);
+    ids[2] = 4;
+    assert_eq!(ids.c, 4);
+    assert_eq!(ids.into_iter().sum::<u32>(), 7);
+}
@@ -0,0 +1,35 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(readonly, index_type = u8)]
+struct Arr {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+#[derive(named_array)]
+#[named_array(readonly, contiguous)]
+#[repr(C)]
+struct Tuple(u32, u32);
+
+#[test]
+fn readonly() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr[0usize], 1);
+    assert_eq!(arr[2u8], 3);
+    assert_eq!(arr[ArrIndex::B], 2);
+    assert_eq!(arr.get(1), Some(&2));
+    assert_eq!(arr.iter().sum::<u32>(), 6);
+    // The fields can still be mutated directly.
+    arr.a = 4;
+    assert_eq!(*arr.first(), 4);
+}
+
+#[test]
+fn readonly_tuple() {
+    let tuple = Tuple(1, 2);
+    assert_eq!(tuple.as_slice(), [1, 2]);
+    assert_eq!(tuple[0..1], [1]);
+    assert_eq!(tuple.map(|x| x * 2).1, 4);
+}
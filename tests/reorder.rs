@@ -0,0 +1,87 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+struct Arr {
+    #[named_array(index = 2)]
+    a: u32,
+    #[named_array(index = 0)]
+    b: u32,
+    #[named_array(index = 1)]
+    c: u32,
+}
+
+#[derive(named_array)]
+struct Tuple(
+    #[named_array(index = 1)] u32,
+    #[named_array(skip)] bool,
+    #[named_array(index = 0)] u32,
+);
+
+#[test]
+fn reorder_named() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr[0], arr.b);
+    assert_eq!(arr[1], arr.c);
+    assert_eq!(arr[2], arr.a);
+    arr[0] = 4;
+    assert_eq!(arr.b, 4);
+    assert_eq!(arr.into_iter().collect::<Vec<_>>(), [4, 3, 1]);
+}
+
+#[test]
+fn reorder_tuple() {
+    let arr = Tuple(1, false, 2);
+    assert_eq!(Tuple::LEN, 2);
+    assert_eq!(arr[0], 2);
+    assert_eq!(arr[1], 1);
+    assert_eq!(arr.get(2), None);
+    assert!(!arr.1);
+}
+
+#[test]
+fn reorder_map() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let mut order = Vec::new();
+    let arr = arr.map(|x| {
+        order.push(x);
+        x * 10
+    });
+    assert_eq!(order, [2, 3, 1]);
+    assert_eq!((arr.a, arr.b, arr.c), (10, 20, 30));
+}
+
+#[test]
+fn reorder_from_array() {
+    let arr = Arr::from([1, 2, 3]);
+    assert_eq!((arr.a, arr.b, arr.c), (3, 1, 2));
+    assert_eq!(<[u32; 3]>::from(arr), [1, 2, 3]);
+}
+
+#[test]
+fn reorder_indexed_iter() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    for (i, value) in arr.indexed_iter() {
+        assert!(core::ptr::eq(value, &arr[i]));
+    }
+    assert_eq!(
+        arr.indexed_iter().collect::<Vec<_>>(),
+        [(0, &2), (1, &3), (2, &1)]
+    );
+}
+
+#[test]
+fn index_agrees_with_index_mut() {
+    let mut arr = Tuple(1, false, 2);
+    for i in 0..Tuple::LEN {
+        let shared: *const u32 = &arr[i];
+        let unique: *const u32 = &mut arr[i];
+        assert_eq!(shared, unique);
+        assert_eq!(Some(shared), arr.get_mut(i).map(|x| x as *const u32));
+    }
+}
+
+#[test]
+fn field_names_reordered() {
+    assert_eq!(Arr::field_names(), ["b", "c", "a"]);
+    assert_eq!(Tuple::field_names(), ["2", "0"]);
+}
@@ -0,0 +1,35 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(saturating)]
+struct Clamped {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+#[derive(named_array)]
+#[named_array(saturating, index_type = i8, index_type = u128)]
+struct Signed(u32, u32, u32);
+
+#[test]
+fn saturating() {
+    let mut clamped = Clamped { a: 1, b: 2, c: 3 };
+    assert_eq!(clamped[0], 1);
+    assert_eq!(clamped[1], 2);
+    assert_eq!(clamped[2], 3);
+    assert_eq!(clamped[3], 3);
+    assert_eq!(clamped[usize::MAX], 3);
+    clamped[10] = 4;
+    assert_eq!(clamped.c, 4);
+}
+
+#[test]
+fn saturating_signed() {
+    let signed = Signed(1, 2, 3);
+    assert_eq!(signed[1i8], 2);
+    assert_eq!(signed[-1i8], 1);
+    assert_eq!(signed[i8::MIN], 1);
+    assert_eq!(signed[i8::MAX], 3);
+    assert_eq!(signed[u128::MAX], 3);
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "serde")]
+
+use named_array::named_array;
+
+#[derive(Debug, PartialEq, named_array)]
+#[named_array(serde)]
+struct Arr {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+#[derive(Debug, PartialEq, named_array)]
+#[named_array(serde)]
+struct Tuple<T>(T, T);
+
+#[derive(Debug, PartialEq, named_array)]
+#[named_array(serde)]
+struct Reordered {
+    #[named_array(index = 1)]
+    a: String,
+    #[named_array(index = 0)]
+    b: String,
+    #[named_array(skip)]
+    c: u32,
+}
+
+/// The generated code has its own visitor type, which mustn't shadow the struct.
+#[derive(Debug, PartialEq, named_array)]
+#[named_array(serde)]
+struct Visitor {
+    a: u32,
+    b: u32,
+}
+
+#[test]
+fn round_trip() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let json = serde_json::to_string(&arr).unwrap();
+    assert_eq!(json, "[1,2,3]");
+    assert_eq!(serde_json::from_str::<Arr>(&json).unwrap(), arr);
+
+    let tuple = Tuple(1.5, -2.0);
+    let json = serde_json::to_string(&tuple).unwrap();
+    assert_eq!(json, "[1.5,-2.0]");
+    assert_eq!(serde_json::from_str::<Tuple<f64>>(&json).unwrap(), tuple);
+
+    let visitor = serde_json::from_str::<Visitor>("[1,2]").unwrap();
+    assert_eq!(visitor, Visitor { a: 1, b: 2 });
+}
+
+#[test]
+fn index_order() {
+    let arr = Reordered {
+        a: String::from("a"),
+        b: String::from("b"),
+        c: 3,
+    };
+    let json = serde_json::to_string(&arr).unwrap();
+    assert_eq!(json, r#"["b","a"]"#);
+    let arr = serde_json::from_str::<Reordered>(&json).unwrap();
+    assert_eq!((arr.a.as_str(), arr.b.as_str(), arr.c), ("a", "b", 0));
+}
+
+#[test]
+fn wrong_length() {
+    let err = serde_json::from_str::<Arr>("[1,2]").unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("invalid length 2, expected an array of length 3"));
+    assert!(serde_json::from_str::<Arr>("[1,2,3,4]").is_err());
+    assert!(serde_json::from_str::<Arr>(r#"{"a":1,"b":2,"c":3}"#).is_err());
+}
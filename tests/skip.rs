@@ -0,0 +1,70 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(expect_len = 3)]
+struct Arr {
+    a: u32,
+    #[named_array(skip)]
+    generation: u64,
+    b: u32,
+    c: u32,
+}
+
+#[derive(named_array)]
+struct Tuple(#[named_array(skip)] &'static str, u32, u32);
+
+#[test]
+fn skip_named() {
+    let mut arr = Arr {
+        a: 1,
+        generation: 7,
+        b: 2,
+        c: 3,
+    };
+    assert_eq!(Arr::LEN, 3);
+    assert_eq!(arr[0], 1);
+    assert_eq!(arr[1], 2);
+    assert_eq!(arr[2], 3);
+    assert_eq!(arr.get(3), None);
+    // The skipped field isn't counted, so the last index is 2 rather than 3.
+    assert_eq!(arr.clamp_index(3), 2);
+    arr[1] = 4;
+    assert_eq!(arr.b, 4);
+    assert_eq!(arr.generation, 7);
+}
+
+#[test]
+fn skip_tuple() {
+    let arr = Tuple("tag", 1, 2);
+    assert_eq!(Tuple::LEN, 2);
+    assert_eq!(arr[0], 1);
+    assert_eq!(arr[1], 2);
+    assert_eq!(arr.0, "tag");
+    assert_eq!(arr.into_iter().collect::<Vec<_>>(), [1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+fn skip_out_of_bounds() {
+    let arr = Tuple("tag", 1, 2);
+    let _ = arr[2];
+}
+
+#[test]
+fn skip_map() {
+    let arr = Arr {
+        a: 1,
+        generation: 7,
+        b: 2,
+        c: 3,
+    };
+    let arr = arr.map(|x| x + 1);
+    assert_eq!((arr.a, arr.b, arr.c), (2, 3, 4));
+    assert_eq!(arr.generation, 7);
+}
+
+#[test]
+fn skip_into_array() {
+    let arr = Tuple("tag", 1, 2);
+    assert_eq!(<[u32; 2]>::from(arr), [1, 2]);
+}
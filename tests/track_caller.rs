@@ -0,0 +1,70 @@
+use std::panic::{self, Location};
+use std::sync::Mutex;
+
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(contiguous, index_type = u8)]
+#[repr(C)]
+struct Arr {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+/// The location of the last panic, recorded by the panic hook.
+static LOCATION: Mutex<Option<(String, u32)>> = Mutex::new(None);
+
+/// Runs `f`, which should panic, and returns the file and line it panicked at.
+fn panic_location(f: impl FnOnce()) -> (String, u32) {
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().unwrap();
+        *LOCATION.lock().unwrap() = Some((location.file().to_owned(), location.line()));
+    }));
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    let _ = panic::take_hook();
+    assert!(result.is_err());
+    LOCATION.lock().unwrap().take().unwrap()
+}
+
+/// Returns the location of the line after the call.
+#[track_caller]
+fn next_line() -> (String, u32) {
+    let location = Location::caller();
+    (location.file().to_owned(), location.line() + 1)
+}
+
+// The panic hook is global, so all the cases are checked in one test, rather than in parallel.
+#[test]
+fn panics_at_caller() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+
+    let expected = next_line();
+    let location = panic_location(|| assert_eq!(arr[3usize], 0));
+    assert_eq!(location, expected);
+    assert_eq!(location.0, file!());
+
+    let expected = next_line();
+    let location = panic_location(|| arr[3usize] = 0);
+    assert_eq!(location, expected);
+
+    let expected = next_line();
+    let location = panic_location(|| assert_eq!(arr[3u8], 0));
+    assert_eq!(location, expected);
+
+    let expected = next_line();
+    let location = panic_location(|| assert_eq!(arr[1..4], [0]));
+    assert_eq!(location, expected);
+
+    let expected = next_line();
+    let location = panic_location(|| assert_eq!(*arr.get_const(3usize), 0));
+    assert_eq!(location, expected);
+
+    let expected = next_line();
+    let location = panic_location(|| arr.swap(0, 3));
+    assert_eq!(location, expected);
+
+    let expected = next_line();
+    let location = panic_location(|| assert_eq!(arr.replace(3, 0), 0));
+    assert_eq!(location, expected);
+}
@@ -0,0 +1,149 @@
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use std::collections::hash_map::DefaultHasher;
+
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(default)]
+struct Defaulted {
+    a: u32,
+    b: u32,
+}
+
+#[derive(named_array)]
+#[named_array(default)]
+struct DefaultedTuple<T>(T, T, #[named_array(skip)] PhantomData<T>);
+
+#[test]
+fn default() {
+    let defaulted = Defaulted::default();
+    assert_eq!(defaulted[0], 0);
+    assert_eq!(defaulted[1], 0);
+
+    let tuple = DefaultedTuple::<String>::default();
+    assert_eq!(tuple[0], "");
+    assert_eq!(tuple[1], "");
+}
+
+#[derive(Debug, named_array)]
+#[named_array(eq)]
+struct Compared {
+    #[named_array(index = 1)]
+    a: u32,
+    #[named_array(index = 0)]
+    b: u32,
+    #[named_array(skip)]
+    c: u32,
+}
+
+#[test]
+fn eq() {
+    let compared = Compared { a: 1, b: 2, c: 3 };
+    // The order of comparison follows the indices, but the result is the same either way.
+    assert_eq!(compared, Compared { a: 1, b: 2, c: 3 });
+    assert_ne!(compared, Compared { a: 1, b: 3, c: 3 });
+    assert_ne!(compared, Compared { a: 2, b: 2, c: 3 });
+    // Skipped fields aren't part of the array, so they aren't compared.
+    assert_eq!(compared, Compared { a: 1, b: 2, c: 4 });
+    assert_eq!(compared.c, 3);
+}
+
+#[derive(named_array)]
+#[named_array(eq, hash)]
+struct Hashed {
+    #[named_array(index = 1)]
+    a: u32,
+    #[named_array(index = 0)]
+    b: u32,
+    #[named_array(skip)]
+    c: u32,
+}
+
+fn hash_of(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn hash() {
+    let hashed = Hashed { a: 1, b: 2, c: 3 };
+    let other = Hashed { a: 1, b: 2, c: 4 };
+    // Skipped fields aren't part of the array, so they aren't hashed either.
+    assert_ne!(hashed.c, other.c);
+    assert!(hashed == other);
+    assert_eq!(hash_of(&hashed), hash_of(&other));
+    // The fields are hashed in index order, the same as the array they convert into.
+    assert_eq!(hash_of(&hashed), hash_of(&[2u32, 1]));
+    assert_ne!(hash_of(&hashed), hash_of(&Hashed { a: 2, b: 1, c: 3 }));
+}
+
+#[derive(Debug, Eq, named_array)]
+#[named_array(eq, ord)]
+struct Ordered {
+    #[named_array(index = 1)]
+    a: u32,
+    #[named_array(index = 0)]
+    b: u32,
+}
+
+#[test]
+fn ord() {
+    let ordered = Ordered { a: 1, b: 2 };
+    // `b` comes first, so it decides the order, even though `a` is declared first.
+    assert!(ordered < Ordered { a: 0, b: 3 });
+    assert!(ordered > Ordered { a: 9, b: 1 });
+    // With the earlier fields equal, the later one decides.
+    assert!(ordered < Ordered { a: 2, b: 2 });
+    assert_eq!(
+        ordered.cmp(&Ordered { a: 1, b: 2 }),
+        core::cmp::Ordering::Equal
+    );
+
+    let mut sorted = [
+        Ordered { a: 1, b: 2 },
+        Ordered { a: 2, b: 1 },
+        Ordered { a: 1, b: 1 },
+    ];
+    sorted.sort();
+    assert_eq!(sorted.map(<[u32; 2]>::from), [[1, 1], [1, 2], [2, 1]]);
+}
+
+struct Opaque(u32);
+
+/// Methods which need the fields to implement a trait shouldn't stop the derive from working when
+/// they don't.
+#[derive(named_array)]
+struct Opaques {
+    a: Opaque,
+    b: Opaque,
+}
+
+#[test]
+fn no_traits() {
+    let opaques = Opaques {
+        a: Opaque(1),
+        b: Opaque(2),
+    };
+    assert_eq!(opaques[1].0, 2);
+    assert_eq!(opaques.a.0 + opaques.b.0, 3);
+}
+
+#[derive(named_array)]
+#[named_array(debug)]
+struct Formatted {
+    #[named_array(index = 2)]
+    a: u32,
+    #[named_array(index = 0)]
+    b: u32,
+    #[named_array(index = 1)]
+    c: u32,
+}
+
+#[test]
+fn debug() {
+    let formatted = Formatted { a: 3, b: 1, c: 2 };
+    assert_eq!(format!("{formatted:?}"), "[1, 2, 3]");
+    assert_eq!(format!("{formatted:#?}"), "[\n    1,\n    2,\n    3,\n]");
+}
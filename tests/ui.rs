@@ -0,0 +1,9 @@
+/// Checks the diagnostics for code which the derive rejects, against the `.stderr` files next to
+/// each case.
+///
+/// Run with `TRYBUILD=overwrite` to update them after changing a message.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
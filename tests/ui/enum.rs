@@ -0,0 +1,9 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+enum Example {
+    A(u32),
+    B(u32),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+union Example {
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
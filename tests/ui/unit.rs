@@ -0,0 +1,6 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+struct Example;
+
+fn main() {}
@@ -0,0 +1,22 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(unchecked_types)]
+struct Aliased {
+    a: Option<()>,
+    b: core::option::Option<()>,
+    c: std::option::Option<()>,
+}
+
+#[test]
+fn aliased_types() {
+    let mut aliased = Aliased {
+        a: Some(()),
+        b: None,
+        c: Some(()),
+    };
+    assert_eq!(aliased[1], None);
+    aliased[1] = Some(());
+    assert_eq!(aliased.b, Some(()));
+    assert!(aliased.iter().all(Option::is_some));
+}
@@ -21,3 +21,518 @@ fn fail_arr() {
     let arr = Arr { a: 1, b: 2, c: 3 };
     let _ = arr[3];
 }
+
+#[test]
+fn get_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.get(1), Some(&2));
+    assert_eq!(arr.get(3), None);
+    *arr.get_mut(2).unwrap() = 4;
+    assert_eq!(arr.c, 4);
+    assert_eq!(arr.get_mut(3), None);
+}
+
+#[test]
+fn try_index_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.try_index(0), Ok(&1));
+    assert_eq!(arr.try_index(3), Err(3));
+    *arr.try_index_mut(2).unwrap() = 4;
+    assert_eq!(arr.c, 4);
+    assert_eq!(arr.try_index_mut(usize::MAX), Err(usize::MAX));
+}
+
+#[test]
+fn clamp_index_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.clamp_index(0), 0);
+    assert_eq!(arr.clamp_index(2), 2);
+    assert_eq!(arr.clamp_index(3), Arr::LEN - 1);
+    assert_eq!(arr[arr.clamp_index(usize::MAX)], 3);
+}
+
+#[test]
+fn get_many_mut_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    let [c, a] = arr.get_many_mut([2, 0]).unwrap();
+    core::mem::swap(c, a);
+    *a += 10;
+    assert_eq!((arr.a, arr.b, arr.c), (13, 2, 1));
+    assert!(arr.get_many_mut([1, 1]).is_none());
+    assert!(arr.get_many_mut([0, 3]).is_none());
+    assert_eq!(arr.get_many_mut([]), Some([]));
+}
+
+#[test]
+fn two_mut_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    let (b, a) = arr.two_mut(1, 0).unwrap();
+    *a += *b;
+    *b = 0;
+    assert_eq!((arr.a, arr.b, arr.c), (3, 0, 3));
+    assert!(arr.two_mut(2, 2).is_none());
+    assert!(arr.two_mut(0, 3).is_none());
+    assert!(arr.two_mut(3, 0).is_none());
+}
+
+#[test]
+fn len_arr() {
+    const BUF: [u8; Arr::LEN] = [0; Arr::LEN];
+    assert_eq!(Arr::LEN, 3);
+    assert_eq!(BUF.len(), 3);
+}
+
+#[test]
+fn field_names_arr() {
+    assert_eq!(Arr::field_names(), ["a", "b", "c"]);
+}
+
+#[test]
+fn len_method() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.len(), 3);
+    assert!(!arr.is_empty());
+}
+
+#[test]
+fn into_iter_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let mut iter = arr.into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(3));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn into_iter_rev() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.into_iter().rev().collect::<Vec<_>>(), [3, 2, 1]);
+}
+
+#[test]
+fn ref_into_iter_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    for x in &mut arr {
+        *x *= 2;
+    }
+    let iter = (&arr).into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.copied().collect::<Vec<_>>(), [2, 4, 6]);
+}
+
+#[test]
+fn iter_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    arr.iter_mut().for_each(|x| *x += 1);
+    let iter = arr.iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.copied().collect::<Vec<_>>(), [2, 3, 4]);
+}
+
+#[test]
+fn iter_mut_rev() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    let mut total = 0;
+    for x in arr.iter_mut().rev() {
+        total += *x;
+        *x = total;
+    }
+    assert_eq!((arr.a, arr.b, arr.c), (6, 5, 3));
+}
+
+/// The iterators are nameable, so they can be stored in other types.
+struct Fields<'a> {
+    iter: core::array::IntoIter<&'a u32, { Arr::LEN }>,
+}
+
+#[test]
+fn iter_types() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let mut fields = Fields { iter: arr.iter() };
+    assert_eq!(fields.iter.next(), Some(&1));
+    drop(fields);
+    let owned: core::array::IntoIter<u32, { Arr::LEN }> = arr.into_iter();
+    assert_eq!(owned.as_slice(), [1, 2, 3]);
+}
+
+#[test]
+fn swap_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    arr.swap(0, 2);
+    assert_eq!((arr.a, arr.b, arr.c), (3, 2, 1));
+    arr.swap(1, 1);
+    assert_eq!((arr.a, arr.b, arr.c), (3, 2, 1));
+    arr.swap(1, 0);
+    assert_eq!((arr.a, arr.b, arr.c), (2, 3, 1));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+fn swap_out_of_bounds() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    arr.swap(0, 3);
+}
+
+#[test]
+fn first_last() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(*arr.first(), 1);
+    assert_eq!(*arr.last(), 3);
+    *arr.first_mut() = 4;
+    *arr.last_mut() = 5;
+    assert_eq!(arr.a, 4);
+    assert_eq!(arr.c, 5);
+}
+
+#[test]
+fn map_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let mut order = Vec::new();
+    let arr = arr.map(|x| {
+        order.push(x);
+        x * 2
+    });
+    assert_eq!(order, [1, 2, 3]);
+    assert_eq!((arr.a, arr.b, arr.c), (2, 4, 6));
+}
+
+#[test]
+fn try_map_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let arr = arr.try_map(|x| x.checked_mul(2).ok_or(x)).unwrap();
+    assert_eq!((arr.a, arr.b, arr.c), (2, 4, 6));
+
+    let mut order = Vec::new();
+    let result = arr.try_map(|x| {
+        order.push(x);
+        if x == 4 {
+            Err(x)
+        } else {
+            Ok(x)
+        }
+    });
+    assert_eq!(result.err(), Some(4));
+    assert_eq!(order, [2, 4]);
+}
+
+#[test]
+fn for_each_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    let mut total = 0;
+    arr.for_each(|x| {
+        total += *x;
+        *x = total;
+    });
+    assert_eq!((arr.a, arr.b, arr.c), (1, 3, 6));
+}
+
+#[test]
+fn fill_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    arr.fill(7);
+    assert_eq!((arr.a, arr.b, arr.c), (7, 7, 7));
+}
+
+#[test]
+fn replace_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.replace(1, 5), 2);
+    assert_eq!((arr.a, arr.b, arr.c), (1, 5, 3));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+fn replace_fail_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    arr.replace(3, 5);
+}
+
+#[test]
+fn checked_swap_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.checked_swap(0, 2), Ok(()));
+    assert_eq!((arr.a, arr.b, arr.c), (3, 2, 1));
+    assert_eq!(arr.checked_swap(1, 1), Ok(()));
+    assert_eq!((arr.a, arr.b, arr.c), (3, 2, 1));
+    assert_eq!(arr.checked_swap(0, 3), Err(3));
+    assert_eq!(arr.checked_swap(4, 1), Err(4));
+    assert_eq!((arr.a, arr.b, arr.c), (3, 2, 1));
+}
+
+#[test]
+fn rotate_arr() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    arr.rotate_left(1);
+    assert_eq!((arr.a, arr.b, arr.c), (2, 3, 1));
+    arr.rotate_right(1);
+    assert_eq!((arr.a, arr.b, arr.c), (1, 2, 3));
+    arr.rotate_left(5);
+    assert_eq!((arr.a, arr.b, arr.c), (3, 1, 2));
+    arr.rotate_right(3);
+    assert_eq!((arr.a, arr.b, arr.c), (3, 1, 2));
+    arr.rotate_right(7);
+    assert_eq!((arr.a, arr.b, arr.c), (2, 3, 1));
+}
+
+#[test]
+fn position_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.position(&2), Some(1));
+    assert_eq!(arr.position(&4), None);
+    let arr = Arr { a: 1, b: 2, c: 2 };
+    assert_eq!(arr.position(&2), Some(1));
+}
+
+#[test]
+fn contains_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    assert!(arr.contains(&3));
+    assert!(!arr.contains(&4));
+}
+
+#[test]
+fn count_where_arr() {
+    let arr = Arr { a: 2, b: 3, c: 4 };
+    assert_eq!(arr.count_where(|x| x % 2 == 0), 2);
+    assert_eq!(arr.count_where(|&x| x > 4), 0);
+}
+
+#[test]
+fn min_max_arr() {
+    let arr = Arr { a: 2, b: 1, c: 3 };
+    assert_eq!(arr.min(), &1);
+    assert_eq!(arr.max(), &3);
+}
+
+#[test]
+fn min_max_ties() {
+    let arr = Arr { a: 2, b: 2, c: 1 };
+    assert!(core::ptr::eq(arr.max(), &arr[0]));
+    let arr = Arr { a: 3, b: 1, c: 1 };
+    assert!(core::ptr::eq(arr.min(), &arr[1]));
+}
+
+#[test]
+fn position_min_max_arr() {
+    let arr = Arr { a: 2, b: 1, c: 3 };
+    assert_eq!(arr.position_min(), 1);
+    assert_eq!(arr.position_max(), 2);
+    let arr = Arr { a: 2, b: 2, c: 2 };
+    assert_eq!(arr.position_min(), 0);
+    assert_eq!(arr.position_max(), 0);
+    let arr = Arr { a: 1, b: 3, c: 3 };
+    assert_eq!(arr.position_max(), 1);
+    let arr = Arr { a: 3, b: 1, c: 1 };
+    assert_eq!(arr.position_min(), 1);
+}
+
+#[test]
+fn sum_product_arr() {
+    let arr = Arr { a: 2, b: 3, c: 4 };
+    assert_eq!(arr.sum(), 9);
+    assert_eq!(arr.product(), 24);
+    assert_eq!(arr.dot(&arr), 29);
+}
+
+/// A number which is `Clone`, but not `Copy`, like a big integer.
+#[derive(Clone, Debug, PartialEq)]
+struct Big(u64);
+
+impl std::ops::Add for Big {
+    type Output = Big;
+    fn add(self, rhs: Big) -> Big {
+        Big(self.0 + rhs.0)
+    }
+}
+
+impl std::ops::Mul for Big {
+    type Output = Big;
+    fn mul(self, rhs: Big) -> Big {
+        Big(self.0 * rhs.0)
+    }
+}
+
+#[derive(named_array)]
+struct Bigs(Big, Big);
+
+#[test]
+fn sum_product_clone() {
+    let bigs = Bigs(Big(2), Big(5));
+    assert_eq!(bigs.sum(), Big(7));
+    assert_eq!(bigs.product(), Big(10));
+    assert_eq!(bigs.dot(&bigs), Big(29));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "attempt to add with overflow")]
+fn sum_overflow() {
+    let arr = Arr {
+        a: u32::MAX,
+        b: 1,
+        c: 0,
+    };
+    arr.sum();
+}
+
+#[test]
+fn get_const_arr() {
+    const ARR: Arr = Arr { a: 1, b: 2, c: 3 };
+    const SECOND: u32 = *ARR.get_const(1);
+    assert_eq!(SECOND, 2);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3")]
+fn get_const_fail_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let _ = arr.get_const(3);
+}
+
+#[test]
+fn reversed_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let reversed = arr.reversed();
+    assert!(core::ptr::eq(reversed[0], &arr.c));
+    assert_eq!(reversed, [&3, &2, &1]);
+}
+
+#[test]
+fn indexed_iter_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    let pairs = arr.indexed_iter().collect::<Vec<_>>();
+    assert_eq!(pairs, [(0, &1), (1, &2), (2, &3)]);
+}
+
+#[test]
+fn split_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.split_first(), (&1, [&2, &3]));
+    assert_eq!(arr.split_last(), (&3, [&1, &2]));
+}
+
+#[test]
+fn pairs_arr() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.pairs(), [(&1, &2), (&2, &3)]);
+}
+
+#[test]
+fn zip_arr() {
+    let (x, y) = (
+        Arr { a: 1, b: 2, c: 3 },
+        Arr {
+            a: 10,
+            b: 20,
+            c: 30,
+        },
+    );
+    assert_eq!(x.zip(&y), [(&1, &10), (&2, &20), (&3, &30)]);
+    assert_eq!(x.zip(&y).map(|(x, y)| x + y), [11, 22, 33]);
+}
+
+#[test]
+fn from_array() {
+    let arr = Arr::from([1, 2, 3]);
+    assert_eq!((arr.a, arr.b, arr.c), (1, 2, 3));
+    let array: [u32; 3] = arr.into();
+    assert_eq!(array, [1, 2, 3]);
+}
+
+#[test]
+fn as_array() {
+    let mut arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.as_array(), [1, 2, 3]);
+    arr[0] = 4;
+    assert_eq!(arr.as_array(), [4, 2, 3]);
+}
+
+#[test]
+fn copied() {
+    let arr = Arr { a: 1, b: 2, c: 3 };
+    assert_eq!(arr.copied(), [1, 2, 3]);
+    assert_eq!(arr.cloned(), [1, 2, 3]);
+}
+
+#[test]
+fn try_from_slice() {
+    let arr = Arr::try_from(&[1, 2, 3][..]).unwrap();
+    assert_eq!((arr.a, arr.b, arr.c), (1, 2, 3));
+
+    let short = Arr::try_from(&[1, 2][..]);
+    assert_eq!(
+        short.err(),
+        Some(ArrLengthError {
+            expected: 3,
+            actual: 2
+        })
+    );
+    let long = Arr::try_from(&[1, 2, 3, 4][..]).err().unwrap();
+    assert_eq!(
+        long.to_string(),
+        "expected a slice of length 3, but its length is 4"
+    );
+}
+
+/// A user type which takes the default name of the length error.
+#[allow(dead_code)]
+struct PairLengthError;
+
+#[derive(named_array)]
+#[named_array(rename_length_error = "PairLenError")]
+struct Pair {
+    a: u32,
+    b: u32,
+}
+
+#[test]
+fn renamed_length_error() {
+    let error: PairLenError = Pair::try_from(&[1][..]).err().unwrap();
+    assert_eq!(
+        error,
+        PairLenError {
+            expected: 2,
+            actual: 1
+        }
+    );
+}
+
+/// A struct with attributes on its fields, which should be ignored.
+#[derive(named_array)]
+struct Documented {
+    /// The first field.
+    a: u32,
+    #[allow(dead_code)]
+    b: u32,
+    /// The last field,
+    /// with a longer comment.
+    #[doc(alias = "last")]
+    c: u32,
+}
+
+#[test]
+fn field_attributes() {
+    let documented = Documented { a: 1, b: 2, c: 3 };
+    assert_eq!(documented[0], 1);
+    assert_eq!(documented[2], 3);
+    assert_eq!(documented.iter().sum::<u32>(), 6);
+    assert_eq!(documented[DocumentedIndex::B], 2);
+}
+
+#[derive(named_array)]
+#[non_exhaustive]
+struct NonExhaustive {
+    a: u32,
+    b: u32,
+}
+
+#[test]
+fn non_exhaustive() {
+    let mut arr = NonExhaustive { a: 1, b: 2 };
+    assert_eq!(arr[1], 2);
+    arr[0] = 3;
+    assert_eq!(arr.a, 3);
+    assert_eq!(<[u32; 2]>::from(arr.map(|x| x + 1)), [4, 3]);
+}
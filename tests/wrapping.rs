@@ -0,0 +1,44 @@
+use named_array::named_array;
+
+#[derive(named_array)]
+#[named_array(wrapping)]
+struct Ring {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+#[derive(named_array)]
+#[named_array(wrapping)]
+struct Single(u32);
+
+#[derive(named_array)]
+#[named_array(wrapping, index_type = i32)]
+struct Signed(u32, u32, u32);
+
+#[test]
+fn wrapping() {
+    let mut ring = Ring { a: 1, b: 2, c: 3 };
+    assert_eq!(ring[0], 1);
+    assert_eq!(ring[2], 3);
+    assert_eq!(ring[Ring::LEN], 1);
+    assert_eq!(ring[Ring::LEN + 1], 2);
+    assert_eq!(ring[usize::MAX], ring[usize::MAX % 3]);
+    ring[5] = 4;
+    assert_eq!(ring.c, 4);
+    // Only indexing wraps, not the methods returning `Option`.
+    assert_eq!(ring.get(3), None);
+
+    assert_eq!(Single(1)[7], 1);
+
+    let signed = Signed(1, 2, 3);
+    assert_eq!(signed[4i32], 2);
+    assert_eq!(signed[1_000_000_000i32], signed[1_000_000_000usize % 3]);
+}
+
+#[test]
+#[should_panic = "index out of bounds: the len is 3 but the index is -1"]
+fn wrapping_negative() {
+    let signed = Signed(1, 2, 3);
+    let _ = signed[-1];
+}